@@ -0,0 +1,121 @@
+//! Generates the solver registry from the day modules, so adding a day never means editing a
+//! dispatch table. Every `src/aoc<year>/day<NN>.rs` that's declared as a module and implements
+//! `Solution` gets an entry, along with the puzzle link from its first line.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+struct Day {
+    year: i32,
+    day: i32,
+    solution: String,
+    link: Option<String>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let src = Path::new("src");
+    let lib = fs::read_to_string(src.join("lib.rs")).unwrap_or_default();
+    let mut days = vec![];
+    for year in years(src) {
+        if !declares(&lib, &format!("aoc{}", year)) {
+            continue;
+        }
+
+        let dir = src.join(format!("aoc{}", year));
+        let modules = fs::read_to_string(dir.join("mod.rs")).unwrap_or_default();
+        for (day, path) in day_files(&dir) {
+            if !declares(&modules, &format!("day{:02}", day)) {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+            match solution(&source) {
+                Some(solution) => days.push(Day {
+                    year,
+                    day,
+                    solution,
+                    link: link(&source),
+                }),
+                None => println!(
+                    "cargo:warning={} doesn't implement Solution, so it isn't registered",
+                    path.display()
+                ),
+            }
+        }
+    }
+    days.sort_by_key(|day| (day.year, day.day));
+
+    let mut registry = String::from("static REGISTRY: &[Registered] = &[\n");
+    for day in &days {
+        writeln!(
+            registry,
+            "    Registered {{ year: {year}, day: {day}, parts: &[1, 2], params: <{path} as Solution>::PARAMS, link: {link:?}, source: \"src/aoc{year}/day{day:02}.rs\", solver: runner::time::<{path}> }},",
+            year = day.year,
+            day = day.day,
+            link = day.link,
+            path = format!("crate::aoc{}::day{:02}::{}", day.year, day.day, day.solution),
+        )
+        .unwrap();
+    }
+    registry.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out, registry).unwrap();
+}
+
+// the `aoc<year>` directories
+fn years(src: &Path) -> Vec<i32> {
+    fs::read_dir(src)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?.strip_prefix("aoc")?.parse().ok()
+        })
+        .collect()
+}
+
+// the `day<NN>.rs` files in a year's directory
+fn day_files(dir: &Path) -> Vec<(i32, std::path::PathBuf)> {
+    fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let day = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect()
+}
+
+fn declares(modules: &str, name: &str) -> bool {
+    modules
+        .lines()
+        .any(|line| line.trim() == format!("pub mod {};", name))
+}
+
+// the type after `impl Solution for`
+fn solution(source: &str) -> Option<String> {
+    let rest = &source[source.find("impl Solution for ")? + "impl Solution for ".len()..];
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+
+    (!name.is_empty()).then_some(name)
+}
+
+// the `// https://...` comment scaffolded days start with
+fn link(source: &str) -> Option<String> {
+    let link = source.lines().next()?.strip_prefix("//")?.trim();
+
+    link.starts_with("https://").then(|| link.to_string())
+}
@@ -0,0 +1,157 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// A part's answer, whatever type the day returns it as. Integers of every width compare equal
+/// when their values are, so an `i32` answer matches a `u64` one. Text that's exactly an integer
+/// counts as that integer, which lets answers read back from `answers.toml`, the submission
+/// history or the command line match the solver's.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// `None` unless both answers are integers, since text has no meaningful order.
+    pub fn compare(&self, other: &Answer) -> Option<Ordering> {
+        match (self, other) {
+            (Answer::Int(answer), Answer::Int(other)) => Some(answer.cmp(other)),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(answer: $int) -> Self {
+                    Answer::Int(answer as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(answer: u128) -> Self {
+        i128::try_from(answer).map_or_else(|_| Answer::Text(answer.to_string()), Answer::Int)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Self {
+        match answer.parse::<i128>() {
+            // only when it prints back the same, so e.g. "007" stays text
+            Ok(int) if int.to_string() == answer => Answer::Int(int),
+            _ => Answer::Text(answer.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Self {
+        Answer::from(answer.as_str())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(answer: &str) -> Result<Self, Self::Err> {
+        Ok(Answer::from(answer))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(answer) => write!(f, "{}", answer),
+            Answer::Text(answer) => f.write_str(answer),
+        }
+    }
+}
+
+// always as a string, TOML integers don't go past i64
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl Visitor<'_> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an answer as a string or an integer")
+            }
+
+            fn visit_str<E: de::Error>(self, answer: &str) -> Result<Answer, E> {
+                Ok(Answer::from(answer))
+            }
+
+            fn visit_i64<E: de::Error>(self, answer: i64) -> Result<Answer, E> {
+                Ok(Answer::from(answer))
+            }
+
+            fn visit_u64<E: de::Error>(self, answer: u64) -> Result<Answer, E> {
+                Ok(Answer::from(answer))
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_widths_test() {
+        assert_eq!(Answer::from(46i32), Answer::from(46u64));
+        assert_eq!(Answer::from(-3isize), Answer::from(-3i8));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(
+            Answer::from(100u64).compare(&Answer::from(42usize)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Answer::from(1).compare(&Answer::from("abc")), None);
+    }
+
+    #[test]
+    fn text_test() {
+        assert_eq!(Answer::from("35"), Answer::from(35));
+        assert_eq!(Answer::from("-35".to_string()), Answer::from(-35));
+        assert_eq!(Answer::from("007"), Answer::Text("007".to_string()));
+        assert_eq!(Answer::from("EHZERUFB").to_string(), "EHZERUFB");
+        assert_eq!("46".parse::<Answer>().unwrap(), Answer::from(46u8));
+    }
+
+    #[test]
+    fn serde_test() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Parts {
+            part1: Answer,
+            part2: Answer,
+        }
+
+        let parts: Parts = toml::from_str("part1 = \"35\"\npart2 = 46\n").unwrap();
+        assert_eq!(parts.part1, Answer::from(35u64));
+        assert_eq!(parts.part2, Answer::from(46i32));
+        assert_eq!(
+            toml::to_string(&parts).unwrap(),
+            "part1 = \"35\"\npart2 = \"46\"\n"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::answer::Answer;
+use crate::solution::Params;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers, kept in `answers.toml` at the crate root as
+///
+/// ```toml
+/// [2023.day05."day05_sample.txt"]
+/// part1 = "35"
+/// part2 = "46"
+/// ```
+///
+/// where the input file is one of the day's files in `src/aocYYYY/res`. Answers for the same
+/// input with some of the day's parameters changed go under `params`, e.g.
+///
+/// ```toml
+/// [2023.day11."day11_sample.txt".params."increase_by=10"]
+/// part2 = "1030"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, BTreeMap<String, Parts>>>);
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Parts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+    /// Keyed by the parameter values, written like `--param` takes them
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, Parts>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    /// File name within the year's `res` directory
+    pub input: String,
+    /// Empty for the day's defaults
+    pub params: Params,
+    pub answer: Answer,
+}
+
+impl Entry {
+    /// Relative to the crate root, like the other puzzle files.
+    pub fn input_path(&self) -> PathBuf {
+        PathBuf::from(format!("src/aoc{}/res/{}", self.year, self.input))
+    }
+
+    /// Whether this is the day's private input rather than one of its samples.
+    pub fn is_real_input(&self) -> bool {
+        self.input == input_file(self.day)
+    }
+}
+
+impl Answers {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(ANSWERS_FILE)
+    }
+
+    /// An empty registry when the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Answers> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        toml::from_str(&contents).with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    pub fn get(
+        &self,
+        year: i32,
+        day: i32,
+        input: &str,
+        params: &Params,
+        part: i32,
+    ) -> Option<&Answer> {
+        let mut parts = self
+            .0
+            .get(&year.to_string())?
+            .get(&day_key(day))?
+            .get(input)?;
+        if !params.is_empty() {
+            parts = parts.params.get(&params.to_string())?;
+        }
+        match part {
+            1 => parts.part1.as_ref(),
+            _ => parts.part2.as_ref(),
+        }
+    }
+
+    /// Records an answer, returning whether anything changed.
+    pub fn set(
+        &mut self,
+        year: i32,
+        day: i32,
+        input: &str,
+        params: &Params,
+        part: i32,
+        answer: &Answer,
+    ) -> bool {
+        let mut parts = self
+            .0
+            .entry(year.to_string())
+            .or_default()
+            .entry(day_key(day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default();
+        if !params.is_empty() {
+            parts = parts.params.entry(params.to_string()).or_default();
+        }
+        let slot = match part {
+            1 => &mut parts.part1,
+            _ => &mut parts.part2,
+        };
+
+        let changed = slot.as_ref() != Some(answer);
+        *slot = Some(answer.clone());
+        changed
+    }
+
+    /// Every answer, ordered by year, day, input, parameters and then part, with the answers for
+    /// the day's defaults first. Fails on a key that can't be read, so a typo in the file can't
+    /// leave an answer unchecked.
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        let mut entries = vec![];
+        for (year_key, days) in &self.0 {
+            let year = year_key
+                .parse()
+                .with_context(|| format!("{} in {} isn't a year", year_key, ANSWERS_FILE))?;
+            for (day_key, inputs) in days {
+                let day = parse_day(day_key).with_context(|| {
+                    format!(
+                        "{}.{} in {} isn't a day like day05",
+                        year_key, day_key, ANSWERS_FILE
+                    )
+                })?;
+                for (input, parts) in inputs {
+                    let mut sets = vec![(Params::default(), parts)];
+                    for (params_key, parts) in &parts.params {
+                        let params = params_key.parse().with_context(|| {
+                            format!(
+                                "Could not read the parameters {:?} for {}.{}.{:?} in {}",
+                                params_key, year_key, day_key, input, ANSWERS_FILE
+                            )
+                        })?;
+                        sets.push((params, parts));
+                    }
+
+                    for (params, parts) in sets {
+                        let answers = [(1, &parts.part1), (2, &parts.part2)];
+                        for (part, answer) in answers {
+                            let Some(answer) = answer else {
+                                continue;
+                            };
+                            entries.push(Entry {
+                                year,
+                                day,
+                                part,
+                                input: input.clone(),
+                                params: params.clone(),
+                                answer: answer.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// The file name of a day's real input, as used in the registry.
+pub fn input_file(day: i32) -> String {
+    format!("day{:02}.txt", day)
+}
+
+/// The file name of a day's first sample, as used in the registry.
+pub fn sample_file(day: i32) -> String {
+    format!("day{:02}_sample.txt", day)
+}
+
+fn day_key(day: i32) -> String {
+    format!("day{:02}", day)
+}
+
+fn parse_day(key: &str) -> Option<i32> {
+    key.strip_prefix("day")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_test() {
+        let mut answers = Answers::default();
+        let defaults = Params::default();
+
+        assert!(answers.set(2023, 5, "day05_sample.txt", &defaults, 1, &Answer::from(35)));
+        assert!(!answers.set(2023, 5, "day05_sample.txt", &defaults, 1, &Answer::from(35)));
+        assert!(answers.set(2023, 5, "day05_sample.txt", &defaults, 2, &Answer::from(46)));
+        assert!(answers.set(2023, 10, "day10.txt", &defaults, 1, &Answer::from(6725)));
+
+        assert_eq!(
+            answers.get(2023, 5, "day05_sample.txt", &defaults, 1),
+            Some(&Answer::from(35))
+        );
+        assert_eq!(answers.get(2023, 5, "day05.txt", &defaults, 1), None);
+        assert_eq!(answers.get(2023, 10, "day10.txt", &defaults, 2), None);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Answers::path(dir.path());
+        let mut answers = Answers::load(&path).unwrap();
+        let defaults = Params::default();
+        answers.set(2023, 5, "day05_sample.txt", &defaults, 1, &Answer::from(35));
+        answers.set(2023, 5, "day05_sample.txt", &defaults, 2, &Answer::from(46));
+        answers.set(2022, 1, "day01.txt", &defaults, 2, &Answer::from(212836));
+        answers.save(&path).unwrap();
+
+        let expected = "\
+[2022.day01.\"day01.txt\"]
+part2 = \"212836\"
+
+[2023.day05.\"day05_sample.txt\"]
+part1 = \"35\"
+part2 = \"46\"
+";
+        assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+        assert_eq!(Answers::load(&path).unwrap(), answers);
+    }
+
+    #[test]
+    fn entries_test() {
+        let answers: Answers = toml::from_str(
+            r#"
+[2023.day10."day10.txt"]
+part1 = "6725"
+
+[2023.day05."day05.txt"]
+part2 = "104070862"
+part1 = "324724204"
+"#,
+        )
+        .unwrap();
+        let entries = answers.entries().unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].day, entries[0].part), (5, 1));
+        assert_eq!(entries[0].answer, Answer::from(324724204));
+        assert_eq!(
+            entries[0].input_path(),
+            PathBuf::from("src/aoc2023/res/day05.txt")
+        );
+        assert!(entries[0].is_real_input());
+        assert_eq!((entries[2].day, entries[2].part), (10, 1));
+    }
+
+    #[test]
+    fn entries_unreadable_key_test() {
+        let answers: Answers = toml::from_str(
+            r#"
+[2023.day11."day11_sample.txt".params."increase_by=ten"]
+part2 = "1030"
+"#,
+        )
+        .unwrap();
+        let error = answers.entries().unwrap_err();
+        assert!(format!("{:#}", error).starts_with(
+            "Could not read the parameters \"increase_by=ten\" for 2023.day11.\"day11_sample.txt\""
+        ));
+
+        let answers: Answers =
+            toml::from_str("[2023.day5x.\"day05.txt\"]\npart1 = \"1\"\n").unwrap();
+        assert_eq!(
+            answers.entries().unwrap_err().to_string(),
+            "2023.day5x in answers.toml isn't a day like day05"
+        );
+    }
+
+    #[test]
+    fn params_test() {
+        let mut answers = Answers::default();
+        let params: Params = "increase_by=10".parse().unwrap();
+        answers.set(
+            2023,
+            11,
+            "day11_sample.txt",
+            &Params::default(),
+            1,
+            &Answer::from(374),
+        );
+        answers.set(
+            2023,
+            11,
+            "day11_sample.txt",
+            &params,
+            2,
+            &Answer::from(1030),
+        );
+
+        assert_eq!(
+            answers.get(2023, 11, "day11_sample.txt", &params, 2),
+            Some(&Answer::from(1030))
+        );
+        assert_eq!(
+            answers.get(2023, 11, "day11_sample.txt", &Params::default(), 2),
+            None
+        );
+
+        let expected = "\
+[2023.day11.\"day11_sample.txt\"]
+part1 = \"374\"
+
+[2023.day11.\"day11_sample.txt\".params.\"increase_by=10\"]
+part2 = \"1030\"
+";
+        assert_eq!(toml::to_string(&answers).unwrap(), expected);
+
+        let entries = answers.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].params.is_empty());
+        assert_eq!((&entries[1].params, entries[1].part), (&params, 2));
+    }
+}
@@ -1,52 +1,40 @@
-use crate::shared;
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use itertools::Itertools;
 
-pub fn part1(input: Vec<String>) -> i32 {
-    shared::group_on_empty(&input)
-        .iter()
-        .map(|group| shared::vec_to_int(group))
-        .map(|vec| vec.iter().sum())
-        .max()
-        .unwrap()
-}
-
-pub fn part2(input: Vec<String>) -> i32 {
-    shared::group_on_empty(&input)
-        .iter()
-        .map(|group| shared::vec_to_int(group))
-        .map(|vec| vec.iter().sum::<i32>())
-        .sorted()
-        .rev()
-        .take(3)
-        .sum()
-}
+pub struct Day01;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
+impl Solution for Day01 {
+    /// Calories carried by each elf
+    type Parsed = Vec<i64>;
+    type Answer = i64;
 
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2022/res/day01_sample.txt");
-        assert_eq!(part1(input), 24000);
-    }
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        // split the raw input rather than taking its blocks, so errors point into the whole input
+        let elves = input
+            .raw()
+            .split("\n\n")
+            .filter(|elf| !elf.trim().is_empty())
+            .map(|elf| {
+                elf.lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| input.parse_at::<i64>(line))
+                    .sum::<Result<i64, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if elves.is_empty() {
+            bail!(input.error_at(input.raw(), "Expected at least one elf's calories"));
+        }
 
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2022/res/day01.txt");
-        assert_eq!(part1(input), 74394);
+        Ok(elves)
     }
 
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2022/res/day01_sample.txt");
-        assert_eq!(part2(input), 45000);
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        *input.iter().max().unwrap()
     }
 
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2022/res/day01.txt");
-        assert_eq!(part2(input), 212836);
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input.iter().sorted().rev().take(3).sum()
     }
 }
@@ -1,10 +1,12 @@
 use crate::aoc2022::day02::Move::{Paper, Rock, Scissors};
 use crate::aoc2022::day02::Outcome::{Draw, Lose, Win};
-use itertools::Itertools;
+use crate::input::{Input, ParseError};
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use std::ops::{Add, Sub};
 
 #[derive(Clone)]
-enum Outcome {
+pub enum Outcome {
     Lose,
     Draw,
     Win,
@@ -34,19 +36,19 @@ impl Add<Move> for Outcome {
     }
 }
 
-impl From<&str> for Outcome {
-    fn from(item: &str) -> Self {
+// the guide's second column read as the outcome instead of a move, X being a loss
+impl From<Move> for Outcome {
+    fn from(item: Move) -> Self {
         match item {
-            "X" => Lose,
-            "Y" => Draw,
-            "Z" => Win,
-            _ => panic!("Could not create outcome from string"),
+            Rock => Lose,
+            Paper => Draw,
+            Scissors => Win,
         }
     }
 }
 
 #[derive(Clone)]
-enum Move {
+pub enum Move {
     Rock,
     Paper,
     Scissors,
@@ -76,13 +78,13 @@ impl Sub<Move> for Move {
     }
 }
 
-impl From<&str> for Move {
-    fn from(item: &str) -> Self {
+impl Move {
+    fn parse(input: &Input, item: &str) -> Result<Self, ParseError> {
         match item {
-            "A" | "X" => Rock,
-            "B" | "Y" => Paper,
-            "C" | "Z" => Scissors,
-            _ => panic!("Could not create move from string"),
+            "A" | "X" => Ok(Rock),
+            "B" | "Y" => Ok(Paper),
+            "C" | "Z" => Ok(Scissors),
+            _ => Err(input.error_at(item, "Expected A, B, C, X, Y or Z")),
         }
     }
 }
@@ -120,64 +122,38 @@ impl Round {
     }
 }
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input
-        .iter()
-        .map(|line| parse_move_line(line))
-        .map(|round| round.score())
-        .sum()
-}
+pub struct Day02;
 
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .iter()
-        .map(|line| parse_outcome_line(line))
-        .map(|round| round.score())
-        .sum()
-}
-
-fn parse_move_line(line: &String) -> Round {
-    line.split(' ')
-        .map(|str| str.into())
-        .next_tuple::<(Move, Move)>()
-        .unwrap()
-        .into()
-}
+impl Solution for Day02 {
+    /// The opponent's move and the guide's second column, as a move
+    type Parsed = Vec<(Move, Move)>;
+    type Answer = i32;
 
-fn parse_outcome_line(line: &String) -> Round {
-    line.split(' ')
-        .next_tuple::<(&str, &str)>()
-        .map(|(opponent, outcome)| (Move::from(opponent), Outcome::from(outcome)))
-        .unwrap()
-        .into()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2022/res/day02_sample.txt");
-        assert_eq!(part1(input), 15);
-    }
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| {
+            let Some((opponent, me)) = line.split_once(' ') else {
+                bail!(input.error_at(&line[line.len()..], "Expected two columns"));
+            };
 
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2022/res/day02.txt");
-        assert_eq!(part1(input), 15632);
+            Ok((Move::parse(input, opponent)?, Move::parse(input, me)?))
+        })
     }
 
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2022/res/day02_sample.txt");
-        assert_eq!(part2(input), 12);
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .cloned()
+            .map(Round::from)
+            .map(|round| round.score())
+            .sum()
     }
 
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2022/res/day02.txt");
-        assert_eq!(part2(input), 14416);
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .cloned()
+            .map(|(opponent, outcome)| Round::from((opponent, Outcome::from(outcome))))
+            .map(|round| round.score())
+            .sum()
     }
 }
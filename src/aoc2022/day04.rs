@@ -1,14 +1,25 @@
-use itertools::Itertools;
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 
 type AssignmentRange = (i32, i32);
 type AssignmentPair = (AssignmentRange, AssignmentRange);
 
-struct Assignments {
+pub struct Assignments {
     worker1: AssignmentRange,
     worker2: AssignmentRange,
 }
 
 impl Assignments {
+    // e.g. `2-4,6-8`
+    fn parse(input: &Input, line: &str) -> Result<Self> {
+        let Some((worker1, worker2)) = line.split_once(',') else {
+            bail!(input.error_at(line, "Expected two ranges separated by a comma"));
+        };
+
+        Ok((parse_range(input, worker1)?, parse_range(input, worker2)?).into())
+    }
+
     fn partial_overlap(&self) -> bool {
         self.worker1.0 <= self.worker2.1 && self.worker1.1 >= self.worker2.0
     }
@@ -19,19 +30,12 @@ impl Assignments {
     }
 }
 
-impl From<String> for Assignments {
-    fn from(line: String) -> Self {
-        line.split(",")
-            .map(|part| {
-                part.split("-")
-                    .map(|num| num.parse::<i32>().unwrap())
-                    .collect_tuple::<AssignmentRange>()
-                    .unwrap()
-            })
-            .collect_tuple::<AssignmentPair>()
-            .unwrap()
-            .into()
-    }
+fn parse_range(input: &Input, range: &str) -> Result<AssignmentRange> {
+    let Some((start, end)) = range.split_once('-') else {
+        bail!(input.error_at(range, "Expected a range like 2-4"));
+    };
+
+    Ok((input.parse_at(start)?, input.parse_at(end)?))
 }
 
 impl From<AssignmentPair> for Assignments {
@@ -43,48 +47,27 @@ impl From<AssignmentPair> for Assignments {
     }
 }
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map_into::<Assignments>()
-        .filter_map(|assignments| assignments.complete_overlap().then(|| assignments))
-        .count() as i32
-}
-
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map_into::<Assignments>()
-        .filter_map(|assignments| assignments.partial_overlap().then(|| assignments))
-        .count() as i32
-}
+pub struct Day04;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2022/res/day04_sample.txt");
-        assert_eq!(part1(input), 2);
-    }
+impl Solution for Day04 {
+    type Parsed = Vec<Assignments>;
+    type Answer = i32;
 
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2022/res/day04.txt");
-        assert_eq!(part1(input), 588);
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| Assignments::parse(input, line))
     }
 
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2022/res/day04_sample.txt");
-        assert_eq!(part2(input), 4);
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .filter(|assignments| assignments.complete_overlap())
+            .count() as i32
     }
 
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2022/res/day04.txt");
-        assert_eq!(part2(input), 911);
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .filter(|assignments| assignments.partial_overlap())
+            .count() as i32
     }
 }
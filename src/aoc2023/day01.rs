@@ -1,66 +1,79 @@
 // https://adventofcode.com/2023/day/1
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input.into_iter().map(parse_line).sum()
-}
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// Each line's calibration value from its digits for part 1, and from its digits and spelled
+    /// out digits for part 2. Part 2's example has lines with only spelled out digits, which
+    /// don't have a value for part 1.
+    type Parsed = Vec<(Option<i32>, i32)>;
+    type Answer = i32;
 
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(|line| {
-            line.replace("one", "o1ne")
-                .replace("two", "t2wo")
-                .replace("three", "t3hree")
-                .replace("four", "f4our")
-                .replace("five", "f5ive")
-                .replace("six", "s6ix")
-                .replace("seven", "s7even")
-                .replace("eight", "e8ight")
-                .replace("nine", "n9ine")
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| {
+            let Some(part2) = parse_line(&spell_out(line)) else {
+                bail!(input.error_at(line, "Expected at least one digit"));
+            };
+
+            Ok((parse_line(line), part2))
         })
-        .map(parse_line)
-        .sum()
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input.iter().filter_map(|(value, _)| *value).sum()
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input.iter().map(|(_, value)| value).sum()
+    }
 }
 
-fn parse_line(line: String) -> i32 {
+fn spell_out(line: &str) -> String {
+    line.replace("one", "o1ne")
+        .replace("two", "t2wo")
+        .replace("three", "t3hree")
+        .replace("four", "f4our")
+        .replace("five", "f5ive")
+        .replace("six", "s6ix")
+        .replace("seven", "s7even")
+        .replace("eight", "e8ight")
+        .replace("nine", "n9ine")
+}
+
+fn parse_line(line: &str) -> Option<i32> {
     let chars = line
         .chars()
         .filter(|c| c.to_digit(10).is_some())
         .collect::<Vec<char>>();
 
     let mut string = "".to_string();
-    string.push(*chars.first().unwrap());
-    string.push(*chars.last().unwrap());
+    string.push(*chars.first()?);
+    string.push(*chars.last()?);
 
-    string.parse::<i32>().unwrap()
+    string.parse::<i32>().ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::parse_input;
+    use crate::input::ParseError;
 
     #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day01_sample.txt");
-        assert_eq!(part1(input), 142);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day01.txt");
-        assert_eq!(part1(input), 54390);
-    }
+    fn parse_no_digit_test() {
+        let error = Day01::parse(&Input::new("1abc2\ntwo1nine\npqrstu")).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
 
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day01_sample2.txt");
-        assert_eq!(part2(input), 281);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day01.txt");
-        assert_eq!(part2(input), 54277);
+        assert_eq!(
+            error.render(),
+            "\
+Expected at least one digit at line 3, column 1
+  |
+3 | pqrstu
+  | ^^^^^^"
+        );
     }
 }
@@ -1,9 +1,12 @@
 // https://adventofcode.com/2023/day/2
 
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{digit1, space1};
-use nom::combinator::{map, map_res};
+use nom::combinator::{map, map_res, value};
 use nom::multi::separated_list1;
 use nom::sequence::{preceded, separated_pair};
 use nom::IResult;
@@ -13,39 +16,58 @@ use std::fmt::Display;
 use std::ops::Mul;
 use std::str::FromStr;
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(|line| game_parser(&line).unwrap().1)
-        .filter(Game::valid)
-        .map(|game| game.id)
-        .sum::<i32>()
-}
-
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(|line| game_parser(&line).unwrap().1)
-        .map(|game| game.get_max_colors())
-        .map(|colors| {
-            colors
-                .into_iter()
-                .map(|revealed| revealed.value())
-                .reduce(Mul::mul)
-                .unwrap()
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Game>;
+    type Answer = i32;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| match game_parser(line) {
+            Ok(("", game)) => Ok(game),
+            Ok((rest, _)) => bail!(input.error_at(rest, "Expected the end of the game")),
+            // nom stops with the rest of the line from where it got stuck
+            Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+                bail!(input.error_at(error.input, "Could not parse the game"))
+            }
+            Err(nom::Err::Incomplete(_)) => {
+                bail!(input.error_at(&line[line.len()..], "The game is cut short"))
+            }
         })
-        .sum::<i32>()
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .filter(|game| game.valid())
+            .map(|game| game.id)
+            .sum::<i32>()
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .map(|game| game.get_max_colors())
+            .map(|colors| {
+                colors
+                    .into_iter()
+                    .map(|revealed| revealed.value())
+                    .reduce(Mul::mul)
+                    .unwrap()
+            })
+            .sum::<i32>()
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-enum Revealed {
+pub enum Revealed {
     RED(i32),
     GREEN(i32),
     BLUE(i32),
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: i32,
     sets: Vec<Vec<Revealed>>,
 }
@@ -69,17 +91,6 @@ impl Display for Revealed {
     }
 }
 
-impl From<(i32, &str)> for Revealed {
-    fn from(value: (i32, &str)) -> Self {
-        match value {
-            (count, "red") => Revealed::RED(count),
-            (count, "blue") => Revealed::BLUE(count),
-            (count, "green") => Revealed::GREEN(count),
-            _ => unimplemented!("Color not supported"),
-        }
-    }
-}
-
 impl Ord for Revealed {
     fn cmp(&self, other: &Self) -> Ordering {
         self.partial_cmp(other).unwrap()
@@ -140,14 +151,21 @@ fn game_id_parser(input: &str) -> IResult<&str, i32> {
     preceded(tag("Game "), digit_parser)(input)
 }
 
-fn color_parser(input: &str) -> IResult<&str, &str> {
-    alt((tag("red"), tag("green"), tag("blue")))(input)
+// the variant to wrap the count in
+type Color = fn(i32) -> Revealed;
+
+fn color_parser(input: &str) -> IResult<&str, Color> {
+    alt((
+        value(Revealed::RED as Color, tag("red")),
+        value(Revealed::GREEN as Color, tag("green")),
+        value(Revealed::BLUE as Color, tag("blue")),
+    ))(input)
 }
 
 fn revealed_parser(input: &str) -> IResult<&str, Revealed> {
     map(
         separated_pair(digit_parser, space1, color_parser),
-        Revealed::from,
+        |(count, color)| color(count),
     )(input)
 }
 
@@ -162,33 +180,3 @@ fn game_parser(input: &str) -> IResult<&str, Game> {
 
     Ok((input, Game { id, sets }))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day02_sample.txt");
-        assert_eq!(part1(input), 8);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day02.txt");
-        assert_eq!(part1(input), 1867);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day02_sample.txt");
-        assert_eq!(part2(input), 2286);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day02.txt");
-        assert_eq!(part2(input), 84538);
-    }
-}
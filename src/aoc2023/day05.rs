@@ -1,67 +1,79 @@
 // https://adventofcode.com/2023/day/5
 
+use crate::input::{parse_at, Input, ParseError};
 use crate::range::{Range, RangeExtensions};
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::VecDeque;
 use std::str::FromStr;
 
-pub fn part1(input: String) -> u64 {
-    let almanac: Almanac = input.parse().unwrap();
+pub struct Day05;
 
-    almanac.find_closest_location()
-}
+impl Solution for Day05 {
+    type Parsed = Almanac;
+    type Answer = u64;
 
-pub fn part2(input: String) -> u64 {
-    let almanac: Almanac = input.parse().unwrap();
-
-    let seeds = almanac.get_seed_ranges();
-    let maps = vec![
-        &almanac.seed_to_soil,
-        &almanac.soil_to_fertilizer,
-        &almanac.fertilizer_to_water,
-        &almanac.water_to_light,
-        &almanac.light_to_temperature,
-        &almanac.temperature_to_humidity,
-        &almanac.humidity_to_location,
-    ];
-
-    let mut seeds_queue = VecDeque::from(seeds);
-    for map in maps.into_iter() {
-        let mut next_seed_ranges = vec![];
-        while let Some(range) = seeds_queue.pop_front() {
-            // there's no mapping required, propagate all ids
-            if !map.contains_range(&range) {
-                next_seed_ranges.push(range.clone());
-                continue;
-            }
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        Ok(input.raw().parse()?)
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input.find_closest_location()
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        let almanac = input;
+
+        let seeds = almanac.get_seed_ranges();
+        let maps = vec![
+            &almanac.seed_to_soil,
+            &almanac.soil_to_fertilizer,
+            &almanac.fertilizer_to_water,
+            &almanac.water_to_light,
+            &almanac.light_to_temperature,
+            &almanac.temperature_to_humidity,
+            &almanac.humidity_to_location,
+        ];
+
+        let mut seeds_queue = VecDeque::from(seeds);
+        for map in maps.into_iter() {
+            let mut next_seed_ranges = vec![];
+            while let Some(range) = seeds_queue.pop_front() {
+                // there's no mapping required, propagate all ids
+                if !map.contains_range(&range) {
+                    next_seed_ranges.push(range.clone());
+                    continue;
+                }
 
-            for entry in map.elements.iter() {
-                // check if the seed range overlaps with any of the mapped ranges
-                if let Some(intersection) = range.intersection(&entry.source) {
-                    next_seed_ranges.push(entry.source_to_dest(&*intersection));
-
-                    // also grab parts of the range that do not overlap and re-add them as ranges for the map
-                    seeds_queue.extend(
-                        range
-                            .subtract(&entry.source)
-                            .iter()
-                            .map(|range| *range.clone())
-                            .collect::<Vec<Range<u64>>>(),
-                    );
-
-                    // since we've re-added the unmatched parts, we can stop checking this range
-                    break;
+                for entry in map.elements.iter() {
+                    // check if the seed range overlaps with any of the mapped ranges
+                    if let Some(intersection) = range.intersection(&entry.source) {
+                        next_seed_ranges.push(entry.source_to_dest(&*intersection));
+
+                        // also grab parts of the range that do not overlap and re-add them as ranges for the map
+                        seeds_queue.extend(
+                            range
+                                .subtract(&entry.source)
+                                .iter()
+                                .map(|range| *range.clone())
+                                .collect::<Vec<Range<u64>>>(),
+                        );
+
+                        // since we've re-added the unmatched parts, we can stop checking this range
+                        break;
+                    }
                 }
             }
+
+            // the seeds queue is empty, we'll move on to the next map and fill it with the mapped seed ranges
+            seeds_queue.extend(next_seed_ranges);
         }
 
-        // the seeds queue is empty, we'll move on to the next map and fill it with the mapped seed ranges
-        seeds_queue.extend(next_seed_ranges);
+        seeds_queue.iter().map(|range| range.0.start).min().unwrap()
     }
-
-    seeds_queue.iter().map(|range| range.0.start).min().unwrap()
 }
 
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<u64>,
     seed_to_soil: Map,
     soil_to_fertilizer: Map,
@@ -73,25 +85,36 @@ struct Almanac {
 }
 
 impl FromStr for Almanac {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let seeds: Vec<_> = input
-            .lines()
-            .skip_while(|&line| !line.contains("seeds"))
-            .take(1)
-            .collect::<String>()
-            .split_whitespace()
-            .skip(1)
-            .map(|num| num.trim().parse::<u64>().unwrap())
-            .collect();
-        let seed_to_soil = create_map(input, "seed-to-soil map:");
-        let soil_to_fertilizer = create_map(input, "soil-to-fertilizer map:");
-        let fertilizer_to_water = create_map(input, "fertilizer-to-water map:");
-        let water_to_light = create_map(input, "water-to-light map:");
-        let light_to_temperature = create_map(input, "light-to-temperature map:");
-        let temperature_to_humidity = create_map(input, "temperature-to-humidity map:");
-        let humidity_to_location = create_map(input, "humidity-to-location map:");
+        let Some(seeds) = input.lines().find_map(|line| line.strip_prefix("seeds:")) else {
+            return Err(ParseError::new(input, "", "Expected a `seeds:` line"));
+        };
+        let seed_texts: Vec<_> = seeds.split_whitespace().collect();
+        // part 2 reads them as pairs of a start and a length
+        match seed_texts.last() {
+            None => {
+                let end = &seeds[seeds.len()..];
+                return Err(ParseError::new(input, end, "Expected at least one seed"));
+            }
+            Some(last) if seed_texts.len() % 2 == 1 => {
+                let message = "Expected seeds in pairs, this one has no length";
+                return Err(ParseError::new(input, last, message));
+            }
+            Some(_) => {}
+        }
+        let seeds = seed_texts
+            .into_iter()
+            .map(|seed| parse_at(input, seed))
+            .collect::<Result<_, _>>()?;
+        let seed_to_soil = create_map(input, "seed-to-soil map:")?;
+        let soil_to_fertilizer = create_map(input, "soil-to-fertilizer map:")?;
+        let fertilizer_to_water = create_map(input, "fertilizer-to-water map:")?;
+        let water_to_light = create_map(input, "water-to-light map:")?;
+        let light_to_temperature = create_map(input, "light-to-temperature map:")?;
+        let temperature_to_humidity = create_map(input, "temperature-to-humidity map:")?;
+        let humidity_to_location = create_map(input, "humidity-to-location map:")?;
 
         Ok(Almanac {
             seeds,
@@ -216,50 +239,34 @@ impl MapEntry {
     }
 }
 
-fn create_map(input: &str, name: &str) -> Map {
+fn create_map(input: &str, name: &str) -> Result<Map, ParseError> {
+    if !input.lines().any(|line| line == name) {
+        let end = &input[input.len()..];
+        return Err(ParseError::new(
+            input,
+            end,
+            format!("Missing the `{}` section", name),
+        ));
+    }
+
     input
         .lines()
         .skip_while(|&line| line != name)
         .skip(1)
         .take_while(|&line| !line.is_empty())
-        .collect::<Vec<_>>()
-        .iter()
-        .map(|&line| {
-            let args: Vec<_> = line
+        .map(|line| {
+            let args: Vec<u64> = line
                 .split_whitespace()
-                .map(|str| str.parse::<u64>().unwrap())
-                .collect();
-            MapEntry::from((args[1], args[0], args[2]))
+                .map(|arg| parse_at(input, arg))
+                .collect::<Result<_, _>>()?;
+            match args[..] {
+                [dest, source, length] => Ok(MapEntry::from((source, dest, length))),
+                _ => Err(ParseError::new(
+                    input,
+                    line,
+                    "Expected the destination start, source start and length",
+                )),
+            }
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::get_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = get_input("aoc2023/res/day05_sample.txt");
-        assert_eq!(part1(input), 35);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = get_input("aoc2023/res/day05.txt");
-        assert_eq!(part1(input), 324724204);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = get_input("aoc2023/res/day05_sample.txt");
-        assert_eq!(part2(input), 46);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = get_input("aoc2023/res/day05.txt");
-        assert_eq!(part2(input), 104070862);
-    }
-}
@@ -1,44 +1,71 @@
 // https://adventofcode.com/2023/day/6
 
-pub fn part1(input: Vec<String>) -> u64 {
-    let times = read_line(0, &input);
-    let distances = read_line(1, &input);
-
-    times
-        .into_iter()
-        .zip(distances)
-        .map(find_winning_times)
-        .reduce(|acc, num| acc * num)
-        .unwrap()
-}
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Races;
+    type Answer = u64;
 
-pub fn part2(input: Vec<String>) -> u64 {
-    let no_spaces: Vec<_> = input
-        .clone()
-        .into_iter()
-        .map(|mut string| {
-            string.retain(|c| !c.is_whitespace());
-            string
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let times = read_line(input, "Time:")?;
+        let distances = read_line(input, "Distance:")?;
+        if times.1.len() != distances.1.len() {
+            bail!(input.error_at(distances.0, "Expected a distance for every time"));
+        }
+
+        Ok(Races {
+            races: times.1.into_iter().zip(distances.1).collect(),
+            race: (join(input, times.0)?, join(input, distances.0)?),
         })
-        .collect();
-    let time = read_line(0, &no_spaces)[0];
-    let distance = read_line(1, &no_spaces)[0];
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .races
+            .iter()
+            .copied()
+            .map(find_winning_times)
+            .product()
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        find_winning_times(input.race)
+    }
+}
 
-    find_winning_times((time, distance))
+/// The times and record distances of the races, and of the single race they make with the
+/// spaces between the numbers ignored.
+pub struct Races {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
 }
 
-fn read_line(line: usize, input: &Vec<String>) -> Vec<u64> {
-    input
-        .iter()
-        .nth(line)
-        .unwrap()
-        .split(":")
-        .nth(1)
-        .unwrap()
-        .trim()
+// the numbers on the line starting with `name`, and the text they're in
+fn read_line<'a>(input: &'a Input, name: &str) -> Result<(&'a str, Vec<u64>)> {
+    let Some(numbers) = input.raw().lines().find_map(|line| line.strip_prefix(name)) else {
+        let end = &input.raw()[input.raw().len()..];
+        bail!(input.error_at(end, format!("Missing the `{}` line", name)));
+    };
+    let parsed = numbers
         .split_whitespace()
-        .map(|str| str.parse::<u64>().unwrap())
-        .collect()
+        .map(|number| input.parse_at(number))
+        .collect::<Result<_, _>>()?;
+
+    Ok((numbers.trim(), parsed))
+}
+
+// the numbers read as one, ignoring the spaces between them
+fn join(input: &Input, numbers: &str) -> Result<u64> {
+    let digits: String = numbers.split_whitespace().collect();
+    let joined = digits.parse().map_err(|error| {
+        input.error_at(numbers, format!("Could not parse {:?}: {}", digits, error))
+    })?;
+
+    Ok(joined)
 }
 
 fn find_winning_times(record: (u64, u64)) -> u64 {
@@ -56,33 +83,3 @@ fn find_winning_times(record: (u64, u64)) -> u64 {
 
     high - low + 1
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day06_sample.txt");
-        assert_eq!(part1(input), 288);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day06.txt");
-        assert_eq!(part1(input), 512295);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day06_sample.txt");
-        assert_eq!(part2(input), 71503);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day06.txt");
-        assert_eq!(part2(input), 36530883);
-    }
-}
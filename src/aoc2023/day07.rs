@@ -3,34 +3,45 @@
 use crate::aoc2023::day07::HandType::{
     FiveOfAKind, FourOfAKind, FullHouse, HighCard, OnePair, ThreeOfAKind, TwoPair,
 };
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::str::FromStr;
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(|str| (&str).parse::<Entry>().unwrap())
-        .sorted()
-        .enumerate()
-        .map(|(index, entry)| entry.bid * ((index as i32) + 1))
-        .sum::<i32>()
-}
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<Entry>;
+    type Answer = i32;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| Entry::parse(input, line))
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .sorted()
+            .enumerate()
+            .map(|(index, entry)| entry.bid * ((index as i32) + 1))
+            .sum::<i32>()
+    }
 
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(|str| str.replace("J", "?"))
-        .map(|str| (&str).parse::<Entry>().unwrap())
-        .sorted()
-        .enumerate()
-        .map(|(index, entry)| entry.bid * ((index as i32) + 1))
-        .sum::<i32>()
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .map(Entry::with_jokers)
+            .sorted()
+            .enumerate()
+            .map(|(index, entry)| entry.bid * ((index as i32) + 1))
+            .sum::<i32>()
+    }
 }
 
 #[derive(Debug)]
-struct Entry {
+pub struct Entry {
     hand: Hand,
     bid: i32,
 }
@@ -54,15 +65,43 @@ impl PartialOrd for Entry {
     }
 }
 
-impl FromStr for Entry {
-    type Err = ();
+impl Entry {
+    // e.g. `32T3K 765`
+    fn parse(input: &Input, line: &str) -> Result<Self> {
+        let Some((cards, bid)) = line.split_once(' ') else {
+            bail!(input.error_at(&line[line.len()..], "Expected a hand and a bid"));
+        };
+        if cards.chars().count() != 5 {
+            bail!(input.error_at(cards, "Expected a hand of five cards"));
+        }
+
+        let cards = cards
+            .char_indices()
+            .map(|(start, card)| {
+                let text = &cards[start..start + card.len_utf8()];
+                Card::parse(card).ok_or_else(|| input.error_at(text, "Unknown card"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Entry {
+            hand: Hand::new(cards),
+            bid: input.parse_at(bid)?,
+        })
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input: Vec<_> = s.split_whitespace().collect();
-        let hand = Hand::new(input[0].chars().map(|c| c.into()).collect::<Vec<Card>>());
-        let bid = input[1].parse::<i32>().unwrap();
+    // the same hand with its jacks played as jokers instead
+    fn with_jokers(&self) -> Entry {
+        let cards = self
+            .hand
+            .cards
+            .iter()
+            .map(|&card| if card == Card::J { Card::JOKER } else { card })
+            .collect();
 
-        Ok(Entry { hand, bid })
+        Entry {
+            hand: Hand::new(cards),
+            bid: self.bid,
+        }
     }
 }
 
@@ -176,10 +215,8 @@ impl From<&Vec<Card>> for HandType {
                 1 => OnePair,
                 _ => HighCard,
             },
-            _ => match num_jokers {
-                5 => FiveOfAKind,
-                _ => unimplemented!("Could not determine hand type"),
-            },
+            // nothing but jokers
+            _ => FiveOfAKind,
         }
     }
 }
@@ -203,9 +240,10 @@ enum Card {
     A,
 }
 
-impl From<char> for Card {
-    fn from(value: char) -> Self {
-        match value {
+impl Card {
+    /// Jacks are never read as jokers, they're only played as them in part 2.
+    fn parse(value: char) -> Option<Self> {
+        let card = match value {
             'A' => Card::A,
             'K' => Card::K,
             'Q' => Card::Q,
@@ -220,38 +258,9 @@ impl From<char> for Card {
             '3' => Card::THREE,
             '2' => Card::TWO,
             '1' => Card::ONE,
-            '?' => Card::JOKER,
-            _ => unimplemented!("Could not parse card"),
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day07_sample.txt");
-        assert_eq!(part1(input), 6440);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day07.txt");
-        assert_eq!(part1(input), 250602641);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day07_sample.txt");
-        assert_eq!(part2(input), 5905);
-    }
+            _ => return None,
+        };
 
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day07.txt");
-        assert_eq!(part2(input), 251037509);
+        Some(card)
     }
 }
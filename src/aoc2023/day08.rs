@@ -1,83 +1,130 @@
 // https://adventofcode.com/2023/day/8
 
 use crate::aoc2023::day08::Instruction::{Left, Right};
-use itertools::Itertools;
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use num::integer::lcm;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Network;
+    type Answer = u64;
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let lines: Vec<_> = input
+            .raw()
+            .lines()
+            .filter(|line| !line.is_empty())
+            .collect();
+        let Some((instructions, nodes)) = lines.split_first() else {
+            bail!(input.error_at(input.raw(), "Expected the instructions"));
+        };
+
+        Ok(Network {
+            instructions: get_instructions(input, instructions)?,
+            map: build_map(input, nodes)?,
+        })
+    }
 
-pub fn part1(input: Vec<String>) -> u64 {
-    let instructions = get_instructions(&input);
-    let map = build_map(&input);
-    let mut key = "AAA".to_string();
-    let mut count = 0;
-    let mut queue = VecDeque::new();
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        let (instructions, map) = (&input.instructions, &input.map);
+        let mut key = "AAA".to_string();
+        let mut count = 0;
+        let mut queue = VecDeque::new();
 
-    loop {
-        queue.extend(instructions.iter().cloned());
+        loop {
+            queue.extend(instructions.iter().cloned());
 
-        while let Some(instruction) = queue.pop_front() {
-            key = get_next_key(&map, &key, instruction);
-            count += 1;
+            while let Some(instruction) = queue.pop_front() {
+                key = get_next_key(map, &key, instruction);
+                count += 1;
 
-            if key == "ZZZ" {
-                return count;
+                if key == "ZZZ" {
+                    return count;
+                }
             }
         }
     }
-}
 
-pub fn part2(input: Vec<String>) -> u64 {
-    let instructions = get_instructions(&input);
-    let map = build_map(&input);
-    let keys = map
-        .keys()
-        .filter(|key| key.ends_with("A"))
-        .map(|key| key.to_owned())
-        .collect::<Vec<String>>();
-    let mut queue = VecDeque::new();
-    queue.extend(instructions.iter().cloned());
-
-    let mut counts = Vec::new();
-    for mut key in keys.into_iter() {
-        let mut count = 0;
-        queue.clear();
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        let (instructions, map) = (&input.instructions, &input.map);
+        let keys = map
+            .keys()
+            .filter(|key| key.ends_with("A"))
+            .map(|key| key.to_owned())
+            .collect::<Vec<String>>();
+        let mut queue = VecDeque::new();
         queue.extend(instructions.iter().cloned());
 
-        while let Some(instruction) = queue.pop_front() {
-            key = get_next_key(&map, &key, instruction);
-            count += 1;
-            if key.ends_with("Z") {
-                counts.push(count);
-                break;
-            }
-
-            if queue.is_empty() {
-                queue.extend(instructions.iter().cloned());
+        let mut counts = Vec::new();
+        for mut key in keys.into_iter() {
+            let mut count = 0;
+            queue.clear();
+            queue.extend(instructions.iter().cloned());
+
+            while let Some(instruction) = queue.pop_front() {
+                key = get_next_key(map, &key, instruction);
+                count += 1;
+                if key.ends_with("Z") {
+                    counts.push(count);
+                    break;
+                }
+
+                if queue.is_empty() {
+                    queue.extend(instructions.iter().cloned());
+                }
             }
         }
+
+        counts.into_iter().reduce(lcm).unwrap()
     }
+}
 
-    counts.into_iter().reduce(lcm).unwrap()
+pub struct Network {
+    instructions: Vec<Instruction>,
+    map: HashMap<String, (String, String)>,
 }
 
-fn get_instructions(input: &Vec<String>) -> Vec<Instruction> {
-    input[0].chars().map(|c| c.into()).collect()
+fn get_instructions(input: &Input, line: &str) -> Result<Vec<Instruction>> {
+    line.char_indices()
+        .map(|(start, c)| match c {
+            'L' => Ok(Left),
+            'R' => Ok(Right),
+            _ => bail!(input.error_at(&line[start..start + c.len_utf8()], "Expected L or R")),
+        })
+        .collect()
 }
 
-fn build_map(input: &Vec<String>) -> HashMap<String, (String, String)> {
-    input
+// e.g. `AAA = (BBB, CCC)`
+fn build_map(input: &Input, lines: &[&str]) -> Result<HashMap<String, (String, String)>> {
+    let nodes = lines
         .iter()
-        .skip(1)
-        .map(|line| {
-            let (key, tuple) = line.split(" = ").collect_tuple().unwrap();
-            let (left, right) = tuple[1..tuple.len() - 1]
-                .split(", ")
-                .collect_tuple()
-                .unwrap();
-
-            (key.to_string(), (left.to_string(), right.to_string()))
+        .map(|&line| {
+            let node = line.split_once(" = ").and_then(|(key, tuple)| {
+                let tuple = tuple.strip_prefix('(')?.strip_suffix(')')?;
+                Some((key, tuple.split_once(", ")?))
+            });
+            node.ok_or_else(|| input.error_at(line, "Expected a node like `AAA = (BBB, CCC)`"))
         })
-        .collect::<HashMap<String, (String, String)>>()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // every step has to lead somewhere
+    let keys: HashSet<_> = nodes.iter().map(|&(key, _)| key).collect();
+    for (_, (left, right)) in &nodes {
+        for next in [left, right] {
+            if !keys.contains(next) {
+                bail!(input.error_at(next, "No node is called this"));
+            }
+        }
+    }
+
+    Ok(nodes
+        .into_iter()
+        .map(|(key, (left, right))| (key.to_string(), (left.to_string(), right.to_string())))
+        .collect())
 }
 
 fn get_next_key(
@@ -94,53 +141,7 @@ fn get_next_key(
 }
 
 #[derive(Copy, Clone)]
-enum Instruction {
+pub enum Instruction {
     Left,
     Right,
 }
-
-impl From<char> for Instruction {
-    fn from(value: char) -> Self {
-        match value {
-            'L' => Left,
-            'R' => Right,
-            _ => unimplemented!("Could not parse instruction"),
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day08_sample.txt");
-        assert_eq!(part1(input), 2);
-    }
-
-    #[test]
-    fn part1_sample2_test() {
-        let input = parse_input("aoc2023/res/day08_sample2.txt");
-        assert_eq!(part1(input), 6);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day08.txt");
-        assert_eq!(part1(input), 20569);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day08_sample3.txt");
-        assert_eq!(part2(input), 6);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day08.txt");
-        assert_eq!(part2(input), 21366921060721);
-    }
-}
@@ -1,39 +1,56 @@
 // https://adventofcode.com/2023/day/9
 
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::Result;
 use itertools::Itertools;
 
-pub fn part1(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(string_to_int)
-        .map(|nums| {
-            calculate_sequences(nums)
-                .into_iter()
-                .map(|nums| nums[nums.len() - 1])
-                .collect::<Vec<_>>()
-        })
-        .map(|nums| nums.iter().rfold(0, |acc, num| acc + num))
-        .sum()
-}
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<Vec<i32>>;
+    type Answer = i32;
 
-pub fn part2(input: Vec<String>) -> i32 {
-    input
-        .into_iter()
-        .map(string_to_int)
-        .map(|nums| {
-            calculate_sequences(nums)
-                .into_iter()
-                .map(|nums| nums[0])
-                .collect::<Vec<_>>()
-        })
-        .map(|nums| nums.iter().rfold(0, |acc, &num| num - acc))
-        .sum()
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        input.parse_lines(|line| string_to_int(input, line))
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .cloned()
+            .map(|nums| {
+                calculate_sequences(nums)
+                    .into_iter()
+                    .map(|nums| nums[nums.len() - 1])
+                    .collect::<Vec<_>>()
+            })
+            .map(|nums| nums.iter().rfold(0, |acc, num| acc + num))
+            .sum()
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        input
+            .iter()
+            .cloned()
+            .map(|nums| {
+                calculate_sequences(nums)
+                    .into_iter()
+                    .map(|nums| nums[0])
+                    .collect::<Vec<_>>()
+            })
+            .map(|nums| nums.iter().rfold(0, |acc, &num| num - acc))
+            .sum()
+    }
 }
 
-fn string_to_int(line: String) -> Vec<i32> {
-    line.split_whitespace()
-        .map(|num| num.parse::<i32>().unwrap())
-        .collect()
+fn string_to_int(input: &Input, line: &str) -> Result<Vec<i32>> {
+    let nums = line
+        .split_whitespace()
+        .map(|num| input.parse_at(num))
+        .collect::<Result<_, _>>()?;
+
+    Ok(nums)
 }
 
 fn calculate_sequences(mut nums: Vec<i32>) -> Vec<Vec<i32>> {
@@ -45,33 +62,3 @@ fn calculate_sequences(mut nums: Vec<i32>) -> Vec<Vec<i32>> {
 
     iterations
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day09_sample.txt");
-        assert_eq!(part1(input), 114);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day09.txt");
-        assert_eq!(part1(input), 1772145754);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day09_sample.txt");
-        assert_eq!(part2(input), 2);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day09.txt");
-        assert_eq!(part2(input), 867);
-    }
-}
@@ -5,30 +5,55 @@ use crate::aoc2023::day10::PipeType::{
     DownLeft, DownRight, Ground, Horizontal, Starting, UpLeft, UpRight, Vertical,
 };
 use crate::graph::Graph;
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::{bail, Result};
 use std::collections::HashMap;
+use std::str::FromStr;
 
-pub fn part1(input: Vec<String>) -> u64 {
-    let grid = input
-        .into_iter()
-        .map(|line| line.chars().collect())
-        .collect();
-    let graph = build_graph(&grid);
-    let starting_node = get_starting_node(&grid);
+pub struct Day10;
 
-    let mut data = HashMap::new();
-    graph.bfs(starting_node, |node, depth| {
-        data.insert(node.id.clone(), depth);
-    });
+impl Solution for Day10 {
+    type Parsed = Tiles;
+    type Answer = u64;
 
-    data.into_values().max().unwrap()
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        let grid = input.grid()?;
+        let raw = input.raw();
+        if let Some((_, second)) = raw.match_indices('S').nth(1) {
+            bail!(input.error_at(second, "Expected only one starting tile"));
+        }
+        let Some(start) = get_starting_node(&grid) else {
+            bail!(input.error_at(&raw[raw.len()..], "Expected a starting tile S"));
+        };
+
+        Ok(Tiles { grid, start })
+    }
+
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        let graph = build_graph(&input.grid);
+        let starting_node = input.start;
+
+        let mut data = HashMap::new();
+        graph.bfs(starting_node, |node, depth| {
+            data.insert(node.id.clone(), depth);
+        });
+
+        data.into_values().max().unwrap()
+    }
+
+    fn part2(_input: &Self::Parsed) -> Self::Answer {
+        0
+    }
 }
 
-pub fn part2(input: Vec<String>) -> i32 {
-    0
+pub struct Tiles {
+    grid: Vec<Vec<PipeType>>,
+    start: (usize, usize),
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Hash)]
-enum PipeType {
+pub enum PipeType {
     Starting,
     Vertical,
     Horizontal,
@@ -39,18 +64,20 @@ enum PipeType {
     Ground,
 }
 
-impl From<char> for PipeType {
-    fn from(value: char) -> Self {
+impl FromStr for PipeType {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
         match value {
-            'S' => Starting,
-            '|' => Vertical,
-            '-' => Horizontal,
-            'L' => DownRight,
-            'J' => DownLeft,
-            'F' => UpRight,
-            '7' => UpLeft,
-            '.' => Ground,
-            _ => unimplemented!("Couldn't parse pipe type"),
+            "S" => Ok(Starting),
+            "|" => Ok(Vertical),
+            "-" => Ok(Horizontal),
+            "L" => Ok(DownRight),
+            "J" => Ok(DownLeft),
+            "F" => Ok(UpRight),
+            "7" => Ok(UpLeft),
+            "." => Ok(Ground),
+            _ => bail!("expected one of S | - L J F 7 ."),
         }
     }
 }
@@ -78,11 +105,11 @@ impl PipeType {
     }
 }
 
-fn build_graph(grid: &Vec<Vec<char>>) -> Graph<(usize, usize), PipeType> {
+fn build_graph(grid: &Vec<Vec<PipeType>>) -> Graph<(usize, usize), PipeType> {
     let graph = Graph::new();
     for (row, rows) in grid.into_iter().enumerate() {
         for (col, val) in rows.into_iter().enumerate() {
-            let pipe_type = PipeType::from(val.clone());
+            let pipe_type = *val;
             if pipe_type == Ground {
                 continue;
             }
@@ -115,44 +142,14 @@ fn build_graph(grid: &Vec<Vec<char>>) -> Graph<(usize, usize), PipeType> {
     graph
 }
 
-fn get_starting_node(grid: &Vec<Vec<char>>) -> (usize, usize) {
+fn get_starting_node(grid: &[Vec<PipeType>]) -> Option<(usize, usize)> {
     for (row, rows) in grid.iter().enumerate() {
         for (col, &c) in rows.iter().enumerate() {
-            if c == 'S' {
-                return (row, col);
+            if c == Starting {
+                return Some((row, col));
             }
         }
     }
 
-    (0, 0)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day10_sample.txt");
-        assert_eq!(part1(input), 8);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day10.txt");
-        assert_eq!(part1(input), 6725);
-    }
-
-    #[test]
-    fn part2_sample_test() {
-        let input = parse_input("aoc2023/res/day10_sample.txt");
-        assert_eq!(part2(input), 0);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day10.txt");
-        assert_eq!(part2(input), 0);
-    }
+    None
 }
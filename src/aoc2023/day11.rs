@@ -1,27 +1,55 @@
 // https://adventofcode.com/2023/day/11
 
+use crate::input::Input;
 use crate::matrix::Matrix;
 use crate::point::Point;
 use crate::range::Range;
+use crate::solution::{Param, Params, Solution};
+use anyhow::Result;
 use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn part1(input: Vec<String>) -> isize {
-    calc(input, 1)
-}
+pub struct Day11;
+
+// how many rows or columns each empty one becomes in part 2, the examples use 10 and 100
+const INCREASE_BY: Param = Param {
+    name: "increase_by",
+    default: 1000000,
+};
+
+impl Solution for Day11 {
+    type Parsed = ExpandedMatrix;
+    type Answer = isize;
+
+    const PARAMS: &'static [Param] = &[INCREASE_BY];
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        Ok(build_and_expand_grid(input.grid()?))
+    }
 
-pub fn part2(input: Vec<String>, increase_by: isize) -> isize {
-    calc(input, increase_by - 1)
+    fn part1(input: &Self::Parsed) -> Self::Answer {
+        total_distance(input, 2)
+    }
+
+    fn part2(input: &Self::Parsed) -> Self::Answer {
+        total_distance(input, INCREASE_BY.default as isize)
+    }
+
+    fn part2_with(input: &Self::Parsed, params: &Params) -> Self::Answer {
+        total_distance(input, params.get(INCREASE_BY.name) as isize)
+    }
 }
 
-fn calc(input: Vec<String>, increase_by: isize) -> isize {
-    let expanded_matrix = build_and_expand_grid(input);
+/// The sum of the distances between every pair of galaxies, with each empty row and column
+/// counting `expansion` times.
+pub fn total_distance(expanded_matrix: &ExpandedMatrix, expansion: isize) -> isize {
+    let increase_by = expansion - 1;
     expanded_matrix
         .matrix
-        .into_iter()
+        .iter()
         .enumerate()
         .flat_map(|(row, rows)| {
-            rows.into_iter().enumerate().filter_map(move |(col, c)| {
+            rows.iter().enumerate().filter_map(move |(col, &c)| {
                 if c == '#' {
                     Some((row as isize, col as isize))
                 } else {
@@ -48,7 +76,7 @@ fn calc(input: Vec<String>, increase_by: isize) -> isize {
         .sum::<_>()
 }
 
-struct ExpandedMatrix {
+pub struct ExpandedMatrix {
     matrix: Matrix<char>,
     empty_rows: HashSet<isize>,
     empty_cols: HashSet<isize>,
@@ -75,11 +103,7 @@ fn get_col_range(a: &Point<isize>, b: &Point<isize>) -> Range<isize> {
     Range::from((a.0 .1, b.0 .1))
 }
 
-fn build_and_expand_grid(input: Vec<String>) -> ExpandedMatrix {
-    let grid = input
-        .into_iter()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<_>>();
+fn build_and_expand_grid(grid: Vec<Vec<char>>) -> ExpandedMatrix {
     let matrix = Matrix(grid);
 
     let empty_rows = get_expanded(&matrix);
@@ -106,39 +130,3 @@ fn get_expanded(matrix: &Matrix<char>) -> HashSet<isize> {
         })
         .collect::<_>()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {
-        let input = parse_input("aoc2023/res/day11_sample.txt");
-        assert_eq!(part1(input), 374);
-    }
-
-    #[test]
-    fn part1_test() {
-        let input = parse_input("aoc2023/res/day11.txt");
-        assert_eq!(part1(input), 9233514);
-    }
-
-    #[test]
-    fn part2_sample_1_test() {
-        let input = parse_input("aoc2023/res/day11_sample.txt");
-        assert_eq!(part2(input, 10), 1030);
-    }
-
-    #[test]
-    fn part2_sample_2_test() {
-        let input = parse_input("aoc2023/res/day11_sample.txt");
-        assert_eq!(part2(input, 100), 8410);
-    }
-
-    #[test]
-    fn part2_test() {
-        let input = parse_input("aoc2023/res/day11.txt");
-        assert_eq!(part2(input, 1000000), 363293506944);
-    }
-}
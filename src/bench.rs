@@ -0,0 +1,406 @@
+use crate::runner;
+use crate::solution::Params;
+use crate::store::InputStore;
+use anyhow::{Context, Result};
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    #[serde(with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        // nearest rank, so it's always one of the samples
+        let p95 = sorted[(count * 95).div_ceil(100) - 1];
+
+        let nanos: Vec<_> = sorted
+            .iter()
+            .map(|sample| sample.as_nanos() as f64)
+            .collect();
+        let mean = nanos.iter().sum::<f64>() / count as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Stats {
+            median,
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PartBench {
+    pub day: i32,
+    pub part: i32,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl PartBench {
+    /// Median time to get from the raw input to this part's answer.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.solve.median
+    }
+}
+
+/// A year's timings, which are also what gets saved as the baseline for the next run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Results {
+    pub year: i32,
+    pub iterations: usize,
+    pub parts: Vec<PartBench>,
+    /// Registered days that were left out for lack of an input
+    #[serde(skip)]
+    pub missing: Vec<i32>,
+}
+
+impl Results {
+    /// `None` when there's no baseline yet.
+    pub fn load(path: &Path) -> Result<Option<Results>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json = fs::read_to_string(path)?;
+        let results = serde_json::from_str(&json)
+            .with_context(|| format!("Could not parse the baseline in {}", path.display()))?;
+
+        Ok(Some(results))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Could not save the baseline to {}", path.display()))
+    }
+
+    /// Each day's parse is only counted once, since both of its parts share it.
+    pub fn total(&self) -> Duration {
+        let parse: Duration = self
+            .parts
+            .iter()
+            .map(|bench| (bench.day, bench.parse.median))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .sum();
+        let solve: Duration = self.parts.iter().map(|bench| bench.solve.median).sum();
+
+        parse + solve
+    }
+
+    fn find(&self, day: i32, part: i32) -> Option<&PartBench> {
+        self.parts
+            .iter()
+            .find(|bench| bench.day == day && bench.part == part)
+    }
+}
+
+#[derive(Display, FromStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum SortBy {
+    Day,
+    Parse,
+    Solve,
+    Total,
+}
+
+/// Baselines are per machine, so they're kept out of the source tree.
+pub fn baseline_path(root: &Path, year: i32) -> PathBuf {
+    root.join(format!("target/aoc-bench/{}.json", year))
+}
+
+/// Runs every registered part of `days` `iterations` times on its input, after one run to warm
+/// up. Days without an input are skipped.
+pub fn bench(
+    root: &Path,
+    inputs: &InputStore,
+    year: i32,
+    days: &[i32],
+    iterations: usize,
+) -> Result<Results> {
+    let mut parts = vec![];
+    let mut missing = vec![];
+
+    for &day in days {
+        let Some(input) = inputs.read(root, year, day)? else {
+            missing.push(day);
+            continue;
+        };
+
+        if let Some(solver) = runner::solver(year, day) {
+            parts.extend(bench_day(day, &input, solver, iterations)?);
+        }
+    }
+
+    Ok(Results {
+        year,
+        iterations,
+        parts,
+        missing,
+    })
+}
+
+// both parts are solved from the same parse each run, so they share its timings
+fn bench_day(
+    day: i32,
+    input: &str,
+    solver: runner::Solver,
+    iterations: usize,
+) -> Result<Vec<PartBench>> {
+    // the real input is always solved with the day's default parameters
+    let (parts, params) = ([1, 2], Params::default());
+    solver(input, &parts, &params)?;
+
+    let mut parse = vec![];
+    let mut solve = vec![vec![]; parts.len()];
+    for _ in 0..iterations {
+        for (index, solved) in solver(input, &parts, &params)?.into_iter().enumerate() {
+            if index == 0 {
+                parse.push(solved.parse_time);
+            }
+            solve[index].push(solved.solve_time);
+        }
+    }
+
+    let parse = Stats::from_samples(&parse);
+    let benches = parts
+        .iter()
+        .zip(solve)
+        .map(|(&part, solve)| PartBench {
+            day,
+            part,
+            parse,
+            solve: Stats::from_samples(&solve),
+        })
+        .collect();
+
+    Ok(benches)
+}
+
+/// The results as a table, with each part's total compared against `baseline` when there is
+/// one. Changes beyond `threshold` percent are flagged.
+pub fn render(
+    results: &Results,
+    baseline: Option<&Results>,
+    sort: SortBy,
+    threshold: f64,
+) -> String {
+    let mut parts: Vec<_> = results.parts.iter().collect();
+    match sort {
+        SortBy::Day => parts.sort_by_key(|bench| (bench.day, bench.part)),
+        SortBy::Parse => parts.sort_by_key(|bench| std::cmp::Reverse(bench.parse.median)),
+        SortBy::Solve => parts.sort_by_key(|bench| std::cmp::Reverse(bench.solve.median)),
+        SortBy::Total => parts.sort_by_key(|bench| std::cmp::Reverse(bench.total())),
+    }
+
+    let mut table = String::new();
+    writeln!(
+        table,
+        "{} benchmarks, {} runs per part\n",
+        results.year, results.iterations
+    )
+    .unwrap();
+    writeln!(
+        table,
+        "Day  Part  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  Baseline",
+        "Parse median", "Parse p95", "Parse stddev", "Solve median", "Solve p95", "Solve stddev"
+    )
+    .unwrap();
+
+    let mut regressions = 0;
+    for bench in parts {
+        let previous = baseline.and_then(|baseline| baseline.find(bench.day, bench.part));
+        let change = match (baseline, previous) {
+            (None, _) => String::new(),
+            (Some(_), None) => "new".to_string(),
+            (Some(_), Some(previous)) => {
+                let percent = percent_change(previous.total(), bench.total());
+                if percent > threshold {
+                    regressions += 1;
+                    format!("{:+.1}% regressed", percent)
+                } else if percent < -threshold {
+                    format!("{:+.1}% improved", percent)
+                } else {
+                    format!("{:+.1}%", percent)
+                }
+            }
+        };
+
+        let line = format!(
+            "{:>3}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  {:>12}  {}",
+            bench.day,
+            bench.part,
+            format_duration(bench.parse.median),
+            format_duration(bench.parse.p95),
+            format_duration(bench.parse.stddev),
+            format_duration(bench.solve.median),
+            format_duration(bench.solve.p95),
+            format_duration(bench.solve.stddev),
+            change
+        );
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    }
+
+    writeln!(
+        table,
+        "\nTotal {} (median parse time of every day and solve time of every part)",
+        format_duration(results.total())
+    )
+    .unwrap();
+    if let Some(baseline) = baseline {
+        writeln!(
+            table,
+            "Baseline total {}, {:+.1}%, {} regressed by more than {}%",
+            format_duration(baseline.total()),
+            percent_change(baseline.total(), results.total()),
+            regressions,
+            threshold
+        )
+        .unwrap();
+    }
+    if !results.missing.is_empty() {
+        let days: Vec<_> = results.missing.iter().map(i32::to_string).collect();
+        writeln!(table, "No input for day {}", days.join(", ")).unwrap();
+    }
+
+    table
+}
+
+fn percent_change(before: Duration, after: Duration) -> f64 {
+    let before = before.as_secs_f64();
+    if before == 0.0 {
+        return 0.0;
+    }
+
+    (after.as_secs_f64() - before) / before * 100.0
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1?}", duration)
+}
+
+// durations are stored as whole nanoseconds to keep the baseline readable
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_nanos(u64::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values
+            .iter()
+            .map(|&value| Duration::from_micros(value))
+            .collect()
+    }
+
+    fn part(day: i32, part: i32, solve_micros: u64) -> PartBench {
+        let stats = |median| Stats {
+            median: Duration::from_micros(median),
+            p95: Duration::from_micros(median),
+            stddev: Duration::ZERO,
+        };
+
+        PartBench {
+            day,
+            part,
+            parse: stats(10),
+            solve: stats(solve_micros),
+        }
+    }
+
+    fn results(parts: Vec<PartBench>) -> Results {
+        Results {
+            year: 2023,
+            iterations: 5,
+            parts,
+            missing: vec![],
+        }
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = Stats::from_samples(&micros(&[5, 1, 3, 2, 4]));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(1414));
+
+        let samples: Vec<_> = (1..=100).collect();
+        let stats = Stats::from_samples(&micros(&samples));
+        assert_eq!(stats.median, Duration::from_nanos(50500));
+        assert_eq!(stats.p95, Duration::from_micros(95));
+
+        assert_eq!(Stats::from_samples(&[]), Stats::default());
+    }
+
+    #[test]
+    fn baseline_round_trip_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = baseline_path(dir.path(), 2023);
+        let results = results(vec![part(1, 1, 100), part(1, 2, 250)]);
+
+        assert_eq!(Results::load(&path).unwrap(), None);
+        results.save(&path).unwrap();
+        assert_eq!(Results::load(&path).unwrap(), Some(results));
+    }
+
+    #[test]
+    fn render_test() {
+        let baseline = results(vec![part(1, 1, 100), part(1, 2, 100), part(2, 1, 100)]);
+        let current = results(vec![
+            part(1, 1, 90),
+            part(1, 2, 190),
+            part(2, 1, 40),
+            part(3, 1, 10),
+        ]);
+        let table = render(&current, Some(&baseline), SortBy::Total, 10.0);
+        let rows: Vec<_> = table.lines().skip(3).take(4).collect();
+
+        assert!(rows[0].starts_with("  1     2"));
+        assert!(rows[0].ends_with("+81.8% regressed"));
+        assert!(rows[1].ends_with("-9.1%"));
+        assert!(rows[2].ends_with("-54.5% improved"));
+        assert!(rows[3].ends_with("new"));
+        assert!(table.contains("\nTotal 360.0µs"));
+        assert!(table.contains("1 regressed by more than 10%"));
+
+        let table = render(&current, None, SortBy::Day, 10.0);
+        assert!(table.lines().nth(3).unwrap().starts_with("  1     1"));
+        assert!(!table.contains("Baseline total"));
+    }
+}
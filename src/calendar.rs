@@ -0,0 +1,238 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use std::str::FromStr;
+
+pub const FIRST_YEAR: i32 = 2015;
+pub const LAST_DAY: i32 = 25;
+
+/// Puzzles unlock at midnight on the site's clock, which is US Eastern standard time.
+pub fn release_clock() -> FixedOffset {
+    FixedOffset::west_opt(5 * 60 * 60).unwrap()
+}
+
+pub fn unlock_time(year: i32, day: i32) -> DateTime<Utc> {
+    release_clock()
+        .with_ymd_and_hms(year, 12, day as u32, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+pub fn is_unlocked(year: i32, day: i32, now: DateTime<Utc>) -> bool {
+    unlock_time(year, day) <= now
+}
+
+/// The most recently unlocked puzzle: today's during an event, otherwise the last day of the
+/// latest event.
+pub fn latest_unlocked(now: DateTime<Utc>) -> (i32, i32) {
+    let today = now.with_timezone(&release_clock());
+
+    if today.month() == 12 {
+        (today.year(), (today.day() as i32).min(LAST_DAY))
+    } else {
+        (today.year() - 1, LAST_DAY)
+    }
+}
+
+/// The next puzzle to unlock after `now`.
+pub fn next_unlock(now: DateTime<Utc>) -> (i32, i32) {
+    let today = now.with_timezone(&release_clock());
+
+    if today.month() == 12 && (today.day() as i32) < LAST_DAY {
+        (today.year(), today.day() as i32 + 1)
+    } else if today.month() == 12 {
+        (today.year() + 1, 1)
+    } else {
+        (today.year(), 1)
+    }
+}
+
+/// Every day of `year` that has unlocked by `now`.
+pub fn unlocked_days(year: i32, now: DateTime<Utc>) -> Vec<i32> {
+    (1..=LAST_DAY)
+        .filter(|&day| is_unlocked(year, day, now))
+        .collect()
+}
+
+/// Fills in whatever wasn't given from the latest unlocked puzzle. The day only defaults for
+/// the latest event, since any other year has no sensible "today".
+pub fn resolve(year: Option<i32>, day: Option<i32>, now: DateTime<Utc>) -> Result<(i32, i32)> {
+    let (latest_year, latest_day) = latest_unlocked(now);
+    let year = resolve_year(year, now)?;
+    let day = match day {
+        Some(day) => day,
+        None if year == latest_year => latest_day,
+        None => bail!("There's no default day for {}, pass one with --day", year),
+    };
+    check_day(day)?;
+
+    Ok((year, day))
+}
+
+/// Like `resolve`, but fills in whatever wasn't given from the next puzzle to unlock, for
+/// waiting on it. Outside December that's the upcoming event rather than the last one.
+pub fn resolve_upcoming(
+    year: Option<i32>,
+    day: Option<i32>,
+    now: DateTime<Utc>,
+) -> Result<(i32, i32)> {
+    let (next_year, next_day) = next_unlock(now);
+    let year = year.unwrap_or(next_year);
+    if year < FIRST_YEAR {
+        bail!("The first event was in {}", FIRST_YEAR);
+    }
+    let day = match day {
+        Some(day) => day,
+        None if year == next_year => next_day,
+        None => bail!("There's no default day for {}, pass one with --day", year),
+    };
+    check_day(day)?;
+
+    Ok((year, day))
+}
+
+/// The given year or the latest event's.
+pub fn resolve_year(year: Option<i32>, now: DateTime<Utc>) -> Result<i32> {
+    let year = year.unwrap_or(latest_unlocked(now).0);
+    if year < FIRST_YEAR {
+        bail!("The first event was in {}", FIRST_YEAR);
+    }
+
+    Ok(year)
+}
+
+fn check_day(day: i32) -> Result<()> {
+    if !(1..=LAST_DAY).contains(&day) {
+        bail!(
+            "Day {} is out of range, puzzles run from day 1 to {}",
+            day,
+            LAST_DAY
+        );
+    }
+
+    Ok(())
+}
+
+/// A selection of days, written as `5`, `1..=25`, `1..8` or a comma separated mix of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(pub Vec<i32>);
+
+impl FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut days = vec![];
+        for part in s.split(',').map(str::trim) {
+            let range = if let Some((start, end)) = part.split_once("..=") {
+                start.trim().parse()?..=end.trim().parse()?
+            } else if let Some((start, end)) = part.split_once("..") {
+                start.trim().parse()?..=end.trim().parse::<i32>()? - 1
+            } else {
+                let day = part.parse()?;
+                day..=day
+            };
+            if range.is_empty() {
+                bail!("{} doesn't include any days", part);
+            }
+
+            for day in range {
+                check_day(day)?;
+                if !days.contains(&day) {
+                    days.push(day);
+                }
+            }
+        }
+        days.sort();
+
+        Ok(Days(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn unlock_time_test() {
+        assert_eq!(unlock_time(2023, 5), utc(2023, 12, 5, 5));
+        assert!(!is_unlocked(2023, 5, utc(2023, 12, 5, 4)));
+        assert!(is_unlocked(2023, 5, utc(2023, 12, 5, 5)));
+    }
+
+    #[test]
+    fn latest_unlocked_test() {
+        // still the 4th on the release clock
+        assert_eq!(latest_unlocked(utc(2023, 12, 5, 4)), (2023, 4));
+        assert_eq!(latest_unlocked(utc(2023, 12, 5, 5)), (2023, 5));
+        assert_eq!(latest_unlocked(utc(2023, 12, 30, 12)), (2023, 25));
+        assert_eq!(latest_unlocked(utc(2024, 1, 1, 4)), (2023, 25));
+        assert_eq!(latest_unlocked(utc(2024, 7, 1, 12)), (2023, 25));
+        assert_eq!(latest_unlocked(utc(2024, 12, 1, 4)), (2023, 25));
+    }
+
+    #[test]
+    fn next_unlock_test() {
+        assert_eq!(next_unlock(utc(2023, 12, 5, 4)), (2023, 5));
+        assert_eq!(next_unlock(utc(2023, 12, 5, 5)), (2023, 6));
+        assert_eq!(next_unlock(utc(2023, 12, 25, 12)), (2024, 1));
+        assert_eq!(next_unlock(utc(2024, 7, 1, 12)), (2024, 1));
+    }
+
+    #[test]
+    fn resolve_test() {
+        let now = utc(2023, 12, 5, 12);
+
+        assert_eq!(resolve(None, None, now).unwrap(), (2023, 5));
+        assert_eq!(resolve(None, Some(3), now).unwrap(), (2023, 3));
+        assert_eq!(resolve(Some(2022), Some(3), now).unwrap(), (2022, 3));
+        assert!(resolve(Some(2022), None, now).is_err());
+        assert!(resolve(None, Some(26), now).is_err());
+        assert!(resolve(Some(2014), Some(1), now).is_err());
+    }
+
+    #[test]
+    fn resolve_upcoming_test() {
+        let summer = utc(2024, 7, 1, 12);
+
+        assert_eq!(resolve_upcoming(None, None, summer).unwrap(), (2024, 1));
+        assert_eq!(resolve_upcoming(None, Some(1), summer).unwrap(), (2024, 1));
+        assert_eq!(
+            resolve_upcoming(Some(2024), None, summer).unwrap(),
+            (2024, 1)
+        );
+        assert!(resolve_upcoming(Some(2023), None, summer).is_err());
+        assert_eq!(
+            resolve_upcoming(None, Some(6), utc(2023, 12, 5, 12)).unwrap(),
+            (2023, 6)
+        );
+        assert_eq!(
+            resolve_upcoming(Some(2023), None, utc(2023, 12, 5, 12)).unwrap(),
+            (2023, 6)
+        );
+        assert!(resolve_upcoming(None, Some(26), summer).is_err());
+    }
+
+    #[test]
+    fn unlocked_days_test() {
+        assert_eq!(unlocked_days(2023, utc(2023, 12, 3, 5)), vec![1, 2, 3]);
+        assert_eq!(unlocked_days(2022, utc(2023, 12, 3, 5)).len(), 25);
+        assert!(unlocked_days(2024, utc(2023, 12, 3, 5)).is_empty());
+    }
+
+    #[test]
+    fn days_test() {
+        let days = |s: &str| s.parse::<Days>().map(|days| days.0);
+
+        assert_eq!(days("5").unwrap(), vec![5]);
+        assert_eq!(days("1..=25").unwrap().len(), 25);
+        assert_eq!(days("1..4").unwrap(), vec![1, 2, 3]);
+        assert_eq!(days("7, 1..=3,2").unwrap(), vec![1, 2, 3, 7]);
+        assert!(days("0..=3").is_err());
+        assert!(days("20..=26").is_err());
+        assert!(days("5..5").is_err());
+        assert!(days("one").is_err());
+    }
+}
@@ -0,0 +1,384 @@
+use anyhow::{bail, Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
+use reqwest::StatusCode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+pub const USER_AGENT: &str = concat!(
+    "aoc-rust/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/natebrunette/aoc-rust)"
+);
+
+pub struct ClientOptions {
+    /// Identifies the tool to the site, which asks automated tools for contact details
+    pub user_agent: String,
+    /// Where fetched inputs are kept, they are never fetched again once cached
+    pub cache_dir: Option<PathBuf>,
+    /// Whether inputs are cached along with everything else. Off when they're kept in an input
+    /// store of their own, which may encrypt them, so no plain copy is left behind.
+    pub cache_inputs: bool,
+    /// Minimum time between two outbound requests
+    pub min_interval: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            user_agent: USER_AGENT.to_string(),
+            cache_dir: None,
+            cache_inputs: true,
+            min_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Talks to the puzzle site, or anything serving the same routes under `base_url`.
+pub struct AocClient {
+    base_url: String,
+    http: reqwest::Client,
+    cache: Option<Cache>,
+    cache_inputs: bool,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl AocClient {
+    /// Without a cookie only the public pages can be fetched.
+    pub fn new(base_url: &str, cookie: Option<&str>, options: &ClientOptions) -> Result<AocClient> {
+        let mut request_headers = HeaderMap::new();
+        if let Some(cookie) = cookie {
+            request_headers.insert(COOKIE, HeaderValue::from_str(cookie)?);
+        }
+        let http = reqwest::Client::builder()
+            .default_headers(request_headers)
+            .user_agent(&options.user_agent)
+            .build()?;
+
+        Ok(AocClient::with_http_client(base_url, http, options))
+    }
+
+    /// Uses `http` as is, so `options.user_agent` is left to however it was built.
+    pub fn with_http_client(
+        base_url: &str,
+        http: reqwest::Client,
+        options: &ClientOptions,
+    ) -> AocClient {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+            cache: options.cache_dir.clone().map(Cache),
+            cache_inputs: options.cache_inputs,
+            min_interval: options.min_interval,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn input_cache(&self) -> Option<&Cache> {
+        self.cache.as_ref().filter(|_| self.cache_inputs)
+    }
+
+    pub async fn fetch_input_data(&self, year: i32, day: i32) -> Result<String> {
+        let cached = self
+            .input_cache()
+            .and_then(|cache| cache.get(&cache.input_path(year, day), None));
+        if let Some(input) = cached {
+            return Ok(input);
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.throttle().await;
+        let res = self.http.get(url).send().await?;
+        let status = res.status();
+        let body = res
+            .text()
+            .await
+            .context("Could not complete request to fetch input data")?;
+
+        check_response(status, &body, year, day)?;
+        if body.trim().is_empty() {
+            bail!("The input for {} day {} came back empty", year, day);
+        }
+
+        if let Some(cache) = self.input_cache() {
+            cache.put(&cache.input_path(year, day), &body)?;
+        }
+
+        Ok(body)
+    }
+
+    /// The site's front page, whose header names the logged in user.
+    pub async fn fetch_home_page(&self) -> Result<String> {
+        let url = format!("{}/", self.base_url);
+        self.throttle().await;
+        let res = self.http.get(url).send().await?;
+        let status = res.status();
+        let body = res
+            .text()
+            .await
+            .context("Could not complete request to fetch the home page")?;
+
+        if !status.is_success() {
+            bail!("Request for the home page failed with {}", status);
+        }
+
+        Ok(body)
+    }
+
+    pub async fn fetch_puzzle_page(&self, year: i32, day: i32) -> Result<String> {
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+        self.throttle().await;
+        let res = self.http.get(url).send().await?;
+        let status = res.status();
+        let body = res
+            .text()
+            .await
+            .context("Could not complete request to fetch the puzzle page")?;
+
+        check_response(status, &body, year, day)?;
+
+        Ok(body)
+    }
+
+    pub async fn submit_answer(
+        &self,
+        year: i32,
+        day: i32,
+        part: i32,
+        answer: &str,
+    ) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let form = [("level", part.to_string()), ("answer", answer.to_string())];
+        self.throttle().await;
+        let res = self.http.post(url).form(&form).send().await?;
+        let status = res.status();
+        let body = res
+            .text()
+            .await
+            .context("Could not complete request to submit answer")?;
+
+        check_response(status, &body, year, day)?;
+
+        Ok(body)
+    }
+
+    /// The site asks for private leaderboards to be fetched at most once every 15 minutes, so a
+    /// cached copy younger than that is returned instead.
+    pub async fn fetch_leaderboard(&self, year: i32, id: u64) -> Result<String> {
+        let cached = self.cache.as_ref().and_then(|cache| {
+            cache.get(&cache.leaderboard_path(year, id), Some(LEADERBOARD_MAX_AGE))
+        });
+        if let Some(json) = cached {
+            return Ok(json);
+        }
+
+        let url = format!(
+            "{}/{}/leaderboard/private/view/{}.json",
+            self.base_url, year, id
+        );
+        self.throttle().await;
+        let res = self.http.get(url).send().await?;
+        let status = res.status();
+        let body = res
+            .text()
+            .await
+            .context("Could not complete request to fetch the leaderboard")?;
+
+        if !status.is_success() {
+            bail!("Request for leaderboard {} failed with {}", id, status);
+        }
+        // without access the site redirects to an HTML page rather than failing
+        if !body.trim_start().starts_with('{') {
+            bail!(
+                "Leaderboard {} didn't come back as JSON, check the id and that the session can view it",
+                id
+            );
+        }
+
+        if let Some(cache) = &self.cache {
+            cache.put(&cache.leaderboard_path(year, id), &body)?;
+        }
+
+        Ok(body)
+    }
+
+    // waits out whatever is left of the minimum interval since the previous request
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            tokio::time::sleep(self.min_interval.saturating_sub(last.elapsed())).await;
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Responses kept on disk, inputs as `<dir>/<year>/day<NN>.txt` and private leaderboards as
+/// `<dir>/<year>/leaderboard_<id>.json`.
+struct Cache(PathBuf);
+
+impl Cache {
+    fn input_path(&self, year: i32, day: i32) -> PathBuf {
+        self.0
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    fn leaderboard_path(&self, year: i32, id: u64) -> PathBuf {
+        self.0
+            .join(year.to_string())
+            .join(format!("leaderboard_{}.json", id))
+    }
+
+    /// Anything older than `max_age` counts as missing.
+    fn get(&self, path: &Path, max_age: Option<Duration>) -> Option<String> {
+        if let Some(max_age) = max_age {
+            let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+            let age = modified.ok()?.elapsed().unwrap_or_default();
+            if age > max_age {
+                return None;
+            }
+        }
+
+        fs::read_to_string(path)
+            .ok()
+            .filter(|contents| !contents.trim().is_empty())
+    }
+
+    fn put(&self, path: &Path, contents: &str) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, contents).with_context(|| format!("Could not cache {}", path.display()))
+    }
+}
+
+/// The site answers a missing or expired session and a locked puzzle with a plain text page,
+/// which must never be mistaken for puzzle input.
+fn check_response(status: StatusCode, body: &str, year: i32, day: i32) -> Result<()> {
+    if status == StatusCode::BAD_REQUEST || body.contains("Please log in") {
+        bail!("The session cookie was rejected, it has probably expired. Store a fresh one with `auth set`");
+    }
+
+    if status == StatusCode::NOT_FOUND || body.contains("before it unlocks") {
+        bail!("{} day {} isn't unlocked yet", year, day);
+    }
+
+    if !status.is_success() {
+        let message = body.lines().next().unwrap_or_default().trim();
+        bail!(
+            "Request for {} day {} failed with {}: {}",
+            year,
+            day,
+            status,
+            message
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use tiny_http::{Response, Server};
+
+    type UserAgents = Arc<std::sync::Mutex<Vec<String>>>;
+
+    // answers every request with `body`, recording the user agent each one was sent with
+    fn stub(body: &'static str) -> (String, UserAgents) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let user_agents = UserAgents::default();
+
+        let recorded = user_agents.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let user_agent = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("User-Agent"))
+                    .map(|header| header.value.to_string())
+                    .unwrap_or_default();
+                recorded.lock().unwrap().push(user_agent);
+                request.respond(Response::from_string(body)).unwrap();
+            }
+        });
+
+        (base_url, user_agents)
+    }
+
+    #[tokio::test]
+    async fn input_cache_test() {
+        let (base_url, requests) = stub("1\n2\n");
+        let dir = tempfile::tempdir().unwrap();
+        let options = ClientOptions {
+            cache_dir: Some(dir.path().to_path_buf()),
+            min_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        let client = AocClient::new(&base_url, None, &options).unwrap();
+
+        assert_eq!(client.fetch_input_data(2023, 5).await.unwrap(), "1\n2\n");
+        assert_eq!(client.fetch_input_data(2023, 5).await.unwrap(), "1\n2\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("2023/day05.txt")).unwrap(),
+            "1\n2\n"
+        );
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let client = AocClient::new(&base_url, None, &options).unwrap();
+        client.fetch_input_data(2023, 5).await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn throttle_and_user_agent_test() {
+        let (base_url, requests) = stub("<article class=\"day-desc\"></article>");
+        let options = ClientOptions {
+            user_agent: "aoc-rust tests".to_string(),
+            min_interval: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let client = AocClient::new(&base_url, None, &options).unwrap();
+
+        let start = Instant::now();
+        client.fetch_puzzle_page(2023, 5).await.unwrap();
+        client.fetch_puzzle_page(2023, 6).await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["aoc-rust tests", "aoc-rust tests"]
+        );
+    }
+
+    #[test]
+    fn check_response_test() {
+        let logged_out = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let locked = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time.";
+
+        assert!(check_response(StatusCode::OK, "1\n2\n", 2023, 5).is_ok());
+        let error = check_response(StatusCode::BAD_REQUEST, logged_out, 2023, 5).unwrap_err();
+        assert!(error.to_string().contains("expired"));
+        let error = check_response(StatusCode::OK, logged_out, 2023, 5).unwrap_err();
+        assert!(error.to_string().contains("expired"));
+        let error = check_response(StatusCode::NOT_FOUND, locked, 2023, 25).unwrap_err();
+        assert_eq!(error.to_string(), "2023 day 25 isn't unlocked yet");
+        let error =
+            check_response(StatusCode::INTERNAL_SERVER_ERROR, "Oops\n", 2023, 5).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("500 Internal Server Error: Oops"));
+    }
+}
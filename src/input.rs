@@ -0,0 +1,321 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A puzzle input with CRLF line endings turned into LF and the trailing newline removed, so a
+/// downloaded input, a sample pasted on Windows and a string literal all look the same.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Input(String);
+
+impl Input {
+    pub fn new(raw: &str) -> Input {
+        Input(raw.replace("\r\n", "\n").trim_end_matches('\n').to_string())
+    }
+
+    pub fn read(path: &Path) -> Result<Input> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+
+        Ok(Input::new(&raw))
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Every line, blank ones included.
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lines().map(str::to_string).collect()
+    }
+
+    /// The groups of lines separated by one or more blank lines.
+    pub fn blocks(&self) -> Vec<Input> {
+        self.0
+            .split("\n\n")
+            .map(|block| block.trim_matches('\n'))
+            .filter(|block| !block.is_empty())
+            .map(Input::new)
+            .collect()
+    }
+
+    /// One row per line with each character parsed as `T`, e.g. `char` or `u8` for digits.
+    pub fn grid<T: FromStr>(&self) -> Result<Vec<Vec<T>>>
+    where
+        T::Err: fmt::Display,
+    {
+        let grid = self
+            .0
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(start, c)| self.parse_at(&line[start..start + c.len_utf8()]))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(grid)
+    }
+
+    /// Every line parsed with `parse`, which gets each one as a slice of the input so the errors
+    /// it makes with `error_at` point at the right place.
+    pub fn parse_lines<T>(&self, parse: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+        self.0.lines().map(parse).collect()
+    }
+
+    /// An error pointing at `text`, which should be a slice of this input, like a line from
+    /// `parse_lines` or part of one. An empty slice points between two characters, e.g. at the
+    /// end of a line that's missing something.
+    pub fn error_at(&self, text: &str, message: impl fmt::Display) -> ParseError {
+        ParseError::new(&self.0, text, message)
+    }
+
+    /// `text` parsed as `T`, or an error pointing at it.
+    pub fn parse_at<T: FromStr>(&self, text: &str) -> Result<T, ParseError>
+    where
+        T::Err: fmt::Display,
+    {
+        parse_at(&self.0, text)
+    }
+
+    /// Every integer in the input in order, with a `-` directly in front making it negative
+    /// unless it follows a digit, so the range `2-4` is 2 and 4. Anything else in between is
+    /// skipped, but an integer too big for an `i64` is an error.
+    pub fn ints(&self) -> Result<Vec<i64>> {
+        let source = self.0.as_str();
+        let mut ints = vec![];
+        let mut offset = 0;
+        while let Some(found) = source[offset..].find(|c: char| c.is_ascii_digit()) {
+            let digits = offset + found;
+            let end = source[digits..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(source.len(), |end| digits + end);
+            let before = &source[..digits];
+            let start = match before.strip_suffix('-') {
+                Some(before) if !before.ends_with(|c: char| c.is_ascii_digit()) => digits - 1,
+                _ => digits,
+            };
+
+            ints.push(self.parse_at(&source[start..end])?);
+            offset = end;
+        }
+
+        Ok(ints)
+    }
+}
+
+/// Where in the input a day's parser gave up, with the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Starting from 1
+    pub line: usize,
+    /// Starting from 1, counted in characters
+    pub column: usize,
+    /// Empty when something is missing rather than wrong
+    pub text: String,
+    /// The whole line `text` is on, for showing it in context
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// An error pointing at `text` within `source`. Parsers only ever slice their input, so
+    /// `text` is found by its address, which tells apart repeats of the same text. Anything that
+    /// isn't a slice of `source` falls back to its first occurrence, or the very start.
+    pub fn new(source: &str, text: &str, message: impl fmt::Display) -> ParseError {
+        let start = (text.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|&start| start + text.len() <= source.len())
+            .or_else(|| source.find(text))
+            .unwrap_or(0);
+        let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |newline| start + newline);
+        // only the first line of text spanning several is underlined
+        let text = &source[start..(start + text.len()).min(line_end)];
+
+        ParseError {
+            message: message.to_string(),
+            line: source[..start].matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            text: text.to_string(),
+            source_line: source[line_start..line_end].to_string(),
+        }
+    }
+
+    /// The message with the line quoted and the offending text underlined, e.g.
+    ///
+    /// ```text
+    /// Unknown card 'X' at line 2, column 3
+    ///   |
+    /// 2 | 32X3K 765
+    ///   |   ^
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = "^".repeat(self.text.chars().count().max(1));
+
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            number,
+            self.source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            underline
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// `text` parsed as `T`, or an error pointing at it within `source`, for parsers working on a
+/// `&str` rather than an `Input`.
+pub fn parse_at<T: FromStr>(source: &str, text: &str) -> Result<T, ParseError>
+where
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|error| {
+        ParseError::new(
+            source,
+            text,
+            format!("Could not parse {:?}: {}", text, error),
+        )
+    })
+}
+
+impl From<&str> for Input {
+    fn from(raw: &str) -> Self {
+        Input::new(raw)
+    }
+}
+
+impl From<String> for Input {
+    fn from(raw: String) -> Self {
+        Input::new(&raw)
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalise_test() {
+        let input = Input::new("1000\r\n2000\r\n\r\n3000\r\n\n");
+        assert_eq!(input.raw(), "1000\n2000\n\n3000");
+        assert_eq!(input, Input::from("1000\n2000\n\n3000\n"));
+        assert_eq!(input.lines(), vec!["1000", "2000", "", "3000"]);
+        assert_eq!(Input::new("").lines(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn blocks_test() {
+        let input = Input::new("1000\n2000\n\n3000\n\n\n4000\n5000\n");
+        let blocks = input.blocks();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].lines(), vec!["1000", "2000"]);
+        assert_eq!(blocks[1].ints().unwrap(), vec![3000]);
+        assert_eq!(blocks[2].raw(), "4000\n5000");
+    }
+
+    #[test]
+    fn grid_test() {
+        let input = Input::new("#.\n.#\n");
+        assert_eq!(
+            input.grid::<char>().unwrap(),
+            vec![vec!['#', '.'], vec!['.', '#']]
+        );
+        assert_eq!(
+            Input::new("12\n34").grid::<u8>().unwrap(),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+
+        let error = Input::new("12\n3x").grid::<u8>().unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.text, "x");
+        assert!(error.to_string().starts_with("Could not parse \"x\""));
+    }
+
+    #[test]
+    fn error_at_test() {
+        let input = Input::new("32T3K 765\nT55J5 684\n32X3K 765");
+        let lines: Vec<_> = input.raw().lines().collect();
+
+        // the third line is told apart from the identical first one by where it is
+        let error = input.error_at(&lines[2][2..3], "Unknown card 'X'");
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.source_line, "32X3K 765");
+        assert_eq!(
+            error.render(),
+            "\
+Unknown card 'X' at line 3, column 3
+  |
+3 | 32X3K 765
+  |   ^"
+        );
+
+        let end = &lines[1][lines[1].len()..];
+        let error = input.error_at(end, "Missing the bid");
+        assert_eq!((error.line, error.column), (2, 10));
+        assert!(error.render().ends_with("2 | T55J5 684\n  |          ^"));
+
+        let bid = input.parse_at::<u32>(&lines[0][6..]).unwrap();
+        assert_eq!(bid, 765);
+        let error = input.parse_at::<u8>(&lines[2][6..]).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 7, "765")
+        );
+    }
+
+    #[test]
+    fn ints_test() {
+        let input = Input::new("seeds: 79 14\n50 98 -2\nx=-13, y=2-3 -4");
+        assert_eq!(
+            input.ints().unwrap(),
+            vec![79, 14, 50, 98, -2, -13, 2, 3, -4]
+        );
+        assert_eq!(Input::new("2-4,6-8").ints().unwrap(), vec![2, 4, 6, 8]);
+        assert_eq!(
+            Input::new("-9223372036854775808").ints().unwrap(),
+            vec![i64::MIN]
+        );
+
+        let error = Input::new("1 2\n3 99999999999999999999 4")
+            .ints()
+            .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "99999999999999999999");
+        assert!(error
+            .render()
+            .ends_with("2 | 3 99999999999999999999 4\n  |   ^^^^^^^^^^^^^^^^^^^^"));
+    }
+}
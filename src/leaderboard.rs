@@ -0,0 +1,242 @@
+use crate::calendar;
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A private leaderboard, as served by `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Deserialize, Debug)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// Stars keyed by day and then by part
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+pub struct DayStars {
+    pub day: i32,
+    pub part1: Option<DateTime<Utc>>,
+    pub part2: Option<DateTime<Utc>>,
+}
+
+impl DayStars {
+    /// How long it took to get from the first star to the second.
+    pub fn part2_delta(&self) -> Option<chrono::Duration> {
+        Some(self.part2? - self.part1?)
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn days(&self) -> Vec<DayStars> {
+        let mut days: Vec<_> = self
+            .completion_day_level
+            .iter()
+            .filter_map(|(day, parts)| {
+                let star = |part: &str| {
+                    let star = parts.get(part)?;
+                    Utc.timestamp_opt(star.get_star_ts, 0).single()
+                };
+
+                Some(DayStars {
+                    day: day.parse().ok()?,
+                    part1: star("1"),
+                    part2: star("2"),
+                })
+            })
+            .collect();
+        days.sort_by_key(|stars| stars.day);
+
+        days
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Leaderboard> {
+        serde_json::from_str(json).context("Could not parse the leaderboard JSON")
+    }
+
+    /// Members by local score, ties going to whoever got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                std::cmp::Reverse(member.stars),
+                member.last_star_ts,
+                member.id,
+            )
+        });
+
+        members
+    }
+
+    pub fn render(&self) -> String {
+        let mut table = String::new();
+        writeln!(table, "{} private leaderboard\n", self.event).unwrap();
+        writeln!(table, "Rank  Score  Stars  Name").unwrap();
+
+        for (rank, member) in self.ranked().iter().enumerate() {
+            writeln!(
+                table,
+                "{:>4}  {:>5}  {:>5}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )
+            .unwrap();
+
+            for stars in member.days() {
+                let delta = stars
+                    .part2_delta()
+                    .map(|delta| format!("+{}", format_duration(delta.num_seconds())))
+                    .unwrap_or_default();
+                let line = format!(
+                    "{:>13} {:>2}  {:<15}  {:<15}  {}",
+                    "day",
+                    stars.day,
+                    format_star(stars.part1),
+                    format_star(stars.part2),
+                    delta
+                );
+                writeln!(table, "{}", line.trim_end()).unwrap();
+            }
+        }
+
+        table
+    }
+}
+
+// on the release clock, so the time of day is also how long after the unlock it was
+fn format_star(star: Option<DateTime<Utc>>) -> String {
+    match star {
+        Some(star) => star
+            .with_timezone(&calendar::release_clock())
+            .format("%b %d %H:%M:%S")
+            .to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn format_duration(secs: i64) -> String {
+    let (days, hours, minutes, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{}d {:02}h {:02}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023-12-01 05:00:00 UTC is the day 1 unlock
+    const JSON: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1701496800,
+                "completion_day_level": {
+                    "2": {"1": {"get_star_ts": 1701496800, "star_index": 3}},
+                    "1": {
+                        "1": {"get_star_ts": 1701407112, "star_index": 1},
+                        "2": {"get_star_ts": 1701407430, "star_index": 2}
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1701410400,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701406860, "star_index": 0},
+                        "2": {"get_star_ts": 1701410400, "star_index": 4}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn ranked_test() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let names: Vec<_> = leaderboard
+            .ranked()
+            .iter()
+            .map(|member| member.display_name())
+            .collect();
+
+        assert_eq!(names, vec!["Alice", "(anonymous user #2)", "Carol"]);
+    }
+
+    #[test]
+    fn days_test() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let days = leaderboard.members["1"].days();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].day, 1);
+        assert_eq!(days[0].part2_delta().unwrap().num_seconds(), 318);
+        assert!(days[1].part2_delta().is_none());
+    }
+
+    #[test]
+    fn render_test() {
+        let expected = "\
+2023 private leaderboard
+
+Rank  Score  Stars  Name
+   1     10      3  Alice
+          day  1  Dec 01 00:05:12  Dec 01 00:10:30  +5m 18s
+          day  2  Dec 02 01:00:00  -
+   2     10      2  (anonymous user #2)
+          day  1  Dec 01 00:01:00  Dec 01 01:00:00  +59m 00s
+   3      0      0  Carol
+";
+
+        assert_eq!(Leaderboard::parse(JSON).unwrap().render(), expected);
+    }
+
+    #[test]
+    fn format_duration_test() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(3 * 3600 + 5), "3h 00m 05s");
+        assert_eq!(format_duration(2 * 86400 + 3600), "2d 01h 00m");
+    }
+}
@@ -1,10 +1,27 @@
+pub mod answer;
+pub mod answers;
 pub mod aoc2022;
 pub mod aoc2023;
+pub mod bench;
+pub mod calendar;
+pub mod client;
 pub mod graph;
+pub mod input;
+pub mod leaderboard;
 pub mod matrix;
 pub mod point;
+pub mod project;
+pub mod puzzle;
 pub mod range;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod session;
 pub mod shared;
+pub mod solution;
+pub mod store;
+pub mod submit;
+pub mod template;
 
 #[cfg(test)]
 pub mod tests;
@@ -1,15 +1,138 @@
-use anyhow::{Context, Result};
-use chrono::{Datelike, Local};
-use clap::Parser;
-use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
+use anyhow::{bail, Context, Result};
+use aoc_rust::answer::Answer;
+use aoc_rust::answers::{Answers, ANSWERS_FILE};
+use aoc_rust::bench::SortBy;
+use aoc_rust::calendar::Days;
+use aoc_rust::client::{AocClient, ClientOptions};
+use aoc_rust::leaderboard::Leaderboard;
+use aoc_rust::scaffold::Outcome;
+use aoc_rust::session::{Session, SessionStore};
+use aoc_rust::solution::{self, Params};
+use aoc_rust::store::{Imported, InputStore};
+use aoc_rust::submit::{History, Verdict};
+use aoc_rust::template::Template;
+use aoc_rust::{
+    answers, bench, calendar, client, project, puzzle, runner, scaffold, session, submit,
+};
+use chrono::Utc;
+use clap::{Parser, Subcommand};
 use std::fs;
-use std::fs::{File, OpenOptions};
+use std::io;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    challenge: ChallengeArgs,
+
+    #[command(flatten)]
+    scaffold: ScaffoldArgs,
+
+    #[command(flatten)]
+    client: ClientArgs,
+
+    #[command(flatten)]
+    inputs: InputArgs,
+
+    /// Crate root to read and write puzzle files under [default: nearest directory with a
+    /// Cargo.toml]
+    #[arg(long, global = true, env = "AOC_ROOT")]
+    root: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct InputArgs {
+    /// Directory puzzle inputs are kept in as `<DIR>/<YEAR>/dayNN.txt`, outside the crate
+    /// [default: the crate's `src/aocYYYY/res`, which git ignores]
+    #[arg(long, global = true, env = "AOC_INPUT_DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Key file to encrypt stored inputs with, a new key is saved there on first use
+    #[arg(long, global = true, env = "AOC_INPUT_KEY")]
+    input_key: Option<PathBuf>,
+}
+
+impl InputArgs {
+    fn store(&self) -> InputStore {
+        InputStore {
+            dir: self.input_dir.clone(),
+            key_file: self.input_key.clone(),
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct ClientArgs {
+    /// Site to fetch puzzles from and submit answers to
+    #[arg(long, global = true, env = "AOC_BASE_URL", default_value = client::BASE_URL)]
+    base_url: String,
+
+    /// User-Agent sent with every request
+    #[arg(long, global = true, env = "AOC_USER_AGENT", default_value = client::USER_AGENT)]
+    user_agent: String,
+
+    /// Directory fetched inputs are cached in, under `profiles/<profile>` for a named profile
+    /// [default: ~/.cache/aoc-rust]
+    #[arg(long, global = true, env = "AOC_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Minimum number of milliseconds between requests to the site
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_MIN_INTERVAL_MS",
+        default_value_t = 1000
+    )]
+    min_interval_ms: u64,
+
+    /// Named session profile, for when there are several accounts. Its stored token is used even
+    /// when AOC_SESSION is set
+    #[arg(long, global = true, env = "AOC_PROFILE", value_parser = session::parse_profile)]
+    profile: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ScaffoldArgs {
+    /// Overwrite the day file, input and samples if they already exist
+    #[arg(long)]
+    force: bool,
+
+    /// Only report the files that would be created or changed
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Count down to the puzzle's unlock and scaffold it as soon as it opens, defaults to the
+    /// next puzzle to unlock
+    #[arg(long)]
+    wait: bool,
+
+    /// Template the day file is generated from, either a built-in style (lines, raw, grid) or
+    /// the name of a `templates/<NAME>.tpl` file. A `templates/day.tpl` replaces the built-in
+    /// template for every style [default: lines]
+    #[arg(long, value_name = "NAME")]
+    template: Option<String>,
+
+    /// Return type of both parts in the generated day file [default: i32]
+    #[arg(long, value_name = "TYPE")]
+    answer_type: Option<String>,
+
+    /// Scaffold several days in one run, e.g. `1..=25` or `1,3,5..=7`
+    #[arg(long, value_name = "DAYS", conflicts_with_all = ["day", "wait", "all_unlocked"])]
+    days: Option<Days>,
+
+    /// Scaffold every day of the year that has unlocked so far
+    #[arg(long, conflicts_with_all = ["day", "wait"])]
+    all_unlocked: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct ChallengeArgs {
     /// Challenge day
     #[arg(short, long)]
     day: Option<i32>,
@@ -19,145 +142,551 @@ struct Args {
     year: Option<i32>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a day's solver against its cached input
+    Run {
+        #[command(flatten)]
+        challenge: ChallengeArgs,
+
+        /// Challenge part, both parts are run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=2))]
+        part: Option<i32>,
+
+        /// Change one of the day's parameters from its default, e.g. `increase_by=100`, and
+        /// check the answers against the ones recorded for those values
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = solution::parse_param)]
+        params: Vec<(String, i64)>,
+    },
+
+    /// Submit an answer, recording the verdict in the day's submission history
+    Submit {
+        #[command(flatten)]
+        challenge: ChallengeArgs,
+
+        /// Challenge part
+        #[arg(short, long, value_parser = clap::value_parser!(i32).range(1..=2))]
+        part: i32,
+
+        /// Answer to submit, the solver's answer is used when omitted
+        answer: Option<String>,
+    },
+
+    /// Time every registered part of a year on its input, comparing against the last run
+    Bench {
+        /// Event year, defaults to the latest event
+        #[arg(short, long)]
+        year: Option<i32>,
+
+        /// Only these days, e.g. `1..=10`
+        #[arg(long, value_name = "DAYS")]
+        days: Option<Days>,
+
+        /// Timed runs of each part
+        #[arg(short = 'n', long, default_value_t = 25, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+
+        /// Order of the table, day or slowest first by parse, solve or total time
+        #[arg(long, default_value_t = SortBy::Day)]
+        sort: SortBy,
+
+        /// Baseline to compare against and save to [default: target/aoc-bench/<YEAR>.json]
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Percent change in a part's median time that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Compare against the baseline without replacing it
+        #[arg(long)]
+        no_save: bool,
+    },
+
+    /// Show a private leaderboard's standings
+    Leaderboard {
+        /// Leaderboard id, the number at the end of its URL
+        #[arg(long, env = "AOC_LEADERBOARD_ID", required_unless_present = "file")]
+        id: Option<u64>,
+
+        /// Event year, defaults to the latest event
+        #[arg(short, long)]
+        year: Option<i32>,
+
+        /// Read the leaderboard JSON from a file instead of the site
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Move the inputs in the crate's `res` directories into the input store
+    Import {
+        /// Only this year's inputs
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
+
+    /// Manage the session token used to talk to the site
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AuthAction {
+    /// Store a session token, either the bare token or `session=...`
+    Set { token: String },
+
+    /// Show where the token comes from and a masked copy of it
+    Show,
+
+    /// Check that the site still accepts the token
+    #[command(alias = "whoami")]
+    Check,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
-    create_challenge_files(args.year, args.day).await?;
+
+    // only the client options are shared, everything else before a subcommand is for scaffolding
+    let scaffolding = args.challenge.year.is_some()
+        || args.challenge.day.is_some()
+        || args.scaffold.force
+        || args.scaffold.dry_run
+        || args.scaffold.wait
+        || args.scaffold.template.is_some()
+        || args.scaffold.answer_type.is_some()
+        || args.scaffold.days.is_some()
+        || args.scaffold.all_unlocked;
+    if args.command.is_some() && scaffolding {
+        bail!(
+            "Scaffolding options can't be combined with a subcommand, pass them after it instead"
+        );
+    }
+
+    // only the commands touching puzzle files need a crate to work in
+    let root = || project::resolve_root(args.root.as_deref());
+    let inputs = args.inputs.store();
+
+    match args.command {
+        Some(Command::Run {
+            challenge,
+            part,
+            params,
+        }) => {
+            let params = params.into_iter().collect();
+            run_challenge(
+                &root()?,
+                &inputs,
+                challenge.year,
+                challenge.day,
+                part,
+                &params,
+            )?
+        }
+        Some(Command::Submit {
+            challenge,
+            part,
+            answer,
+        }) => {
+            submit_challenge(
+                &args.client,
+                &root()?,
+                &inputs,
+                challenge.year,
+                challenge.day,
+                part,
+                answer,
+            )
+            .await?
+        }
+        Some(Command::Bench {
+            year,
+            days,
+            iterations,
+            sort,
+            baseline,
+            threshold,
+            no_save,
+        }) => {
+            let year = calendar::resolve_year(year, Utc::now())?;
+            let days = days.map_or_else(|| runner::registered_days(year), |days| days.0);
+            let options = BenchOptions {
+                iterations: iterations as usize,
+                sort,
+                baseline,
+                threshold,
+                save: !no_save,
+            };
+            bench(&root()?, &inputs, year, &days, options)?
+        }
+        Some(Command::Leaderboard { id, year, file }) => {
+            leaderboard(&args.client, id, year, file).await?
+        }
+        Some(Command::Import { year }) => import(&root()?, &inputs, year)?,
+        Some(Command::Auth { action }) => auth(&args.client, action).await?,
+        None => {
+            let (challenge, scaffold) = (args.challenge, args.scaffold);
+            create_challenge_files(&args.client, &root()?, inputs, challenge, scaffold).await?
+        }
+    }
 
     Ok(())
 }
 
-async fn create_challenge_files(year: Option<i32>, day: Option<i32>) -> Result<()> {
-    let year = year.unwrap_or_else(|| Local::now().year()).to_string();
-    let day = day.unwrap_or_else(|| Local::now().day() as i32);
-    let day_str = format!("{:02}", day);
+fn run_challenge(
+    root: &Path,
+    inputs: &InputStore,
+    year: Option<i32>,
+    day: Option<i32>,
+    part: Option<i32>,
+    params: &Params,
+) -> Result<()> {
+    let (year, day) = calendar::resolve(year, day, Utc::now())?;
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let answers = Answers::load(&Answers::path(root))?;
+    let input_file = answers::input_file(day);
 
-    // Directory paths
-    let year_dir = format!("aoc{}", year);
-    let dir_path = format!("src/{}", year_dir);
-    let res_path = format!("{}/res", dir_path);
-    let new_year = !Path::new(&dir_path).exists();
+    // both parts come from a single parse
+    for solved in runner::run(root, inputs, year, day, &parts, params)? {
+        println!(
+            "{} day {} part {}: {} (parsed in {:?}, solved in {:?})",
+            year, day, solved.part, solved.answer, solved.parse_time, solved.solve_time
+        );
+        match answers.get(year, day, &input_file, params, solved.part) {
+            Some(known) if known == &solved.answer => println!("  matches {}", ANSWERS_FILE),
+            Some(known) => println!("  doesn't match {} from {}", known, ANSWERS_FILE),
+            None => {}
+        }
+    }
 
-    // if we're starting a new year, update the lib file
-    if new_year {
-        let lib_file = "src/lib.rs";
-        let lib_contents = fs::read_to_string(lib_file)?;
-        let new_contents = format!("{}\n{}", format!("pub mod {};", year_dir), lib_contents);
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(lib_file)?;
+    Ok(())
+}
 
-        file.write_all(new_contents.as_bytes())?;
+async fn submit_challenge(
+    client_args: &ClientArgs,
+    root: &Path,
+    inputs: &InputStore,
+    year: Option<i32>,
+    day: Option<i32>,
+    part: i32,
+    answer: Option<String>,
+) -> Result<()> {
+    let (year, day) = calendar::resolve(year, day, Utc::now())?;
+    let answer = match answer {
+        Some(answer) => Answer::from(answer),
+        None => {
+            runner::run(root, inputs, year, day, &[part], &Params::default())?
+                .remove(0)
+                .answer
+        }
+    };
+
+    let client = build_client(client_args, true, None)?;
+    let mut history = History::load(&root.join(History::path(year, day)))?;
+    let verdict = submit::submit(&client, &mut history, year, day, part, &answer).await?;
+
+    println!(
+        "{} day {} part {}: {} is {}",
+        year,
+        day,
+        part,
+        answer,
+        verdict.describe()
+    );
+
+    // verified answers go in the registry the answers harness checks
+    if verdict == Verdict::Correct {
+        let path = Answers::path(root);
+        let mut answers = Answers::load(&path)?;
+        let input_file = answers::input_file(day);
+        if answers.set(year, day, &input_file, &Params::default(), part, &answer) {
+            answers.save(&path)?;
+            println!("Recorded the answer in {}", path.display());
+        }
     }
 
-    // Create directories if they don't exist
-    fs::create_dir_all(&dir_path)?;
-    fs::create_dir_all(&res_path)?;
+    // solving part 1 unlocks the part 2 description
+    if verdict == Verdict::Correct && part == 1 {
+        puzzle::save_description(&client, root, year, day).await?;
+    }
+
+    Ok(())
+}
 
-    // File paths
-    let day_file_path = format!("{}/day{}.rs", dir_path, day_str);
-    let mod_file_path = format!("{}/mod.rs", dir_path);
-    let sample_file_path = format!("{}/day{}_sample.txt", res_path, day_str);
-    let input_file_path = format!("{}/day{}.txt", res_path, day_str);
+async fn create_challenge_files(
+    client_args: &ClientArgs,
+    root: &Path,
+    inputs: InputStore,
+    challenge: ChallengeArgs,
+    scaffold: ScaffoldArgs,
+) -> Result<()> {
+    let template = scaffold.template.as_deref().unwrap_or("lines");
+    let defaults = scaffold::Options::default();
+    let options = scaffold::Options {
+        force: scaffold.force,
+        dry_run: scaffold.dry_run,
+        template: Template::load(template, root)?,
+        answer_type: scaffold.answer_type.unwrap_or(defaults.answer_type),
+        inputs,
+    };
 
-    // Create or truncate files
-    let mut day_file = File::create(&day_file_path)?;
-    let mut input_file = File::create(&input_file_path)?;
-    let _ = File::create(&sample_file_path)?;
+    // a dry run never fetches, so it shouldn't need a cookie either
+    let client = build_client(client_args, !options.dry_run, Some(&options.inputs))?;
 
-    // Write to input file
-    writeln!(input_file, "{}", fetch_challenge_input(&year, day).await?)?;
+    let now = Utc::now();
+    if scaffold.days.is_some() || scaffold.all_unlocked {
+        let year = calendar::resolve_year(challenge.year, now)?;
+        let days = match scaffold.days {
+            Some(days) => days.0,
+            None => calendar::unlocked_days(year, now),
+        };
+        if days.is_empty() {
+            bail!("Nothing has unlocked for {} yet", year);
+        }
 
-    // Write to day.rs file
-    writeln!(day_file, "{}", day_template(&year, &day_str))?;
+        return create_days(&client, root, year, &days, &options).await;
+    }
 
-    // Check if mod.rs exists, create it if it doesn't
-    if !Path::new(&mod_file_path).exists() {
-        File::create(&mod_file_path)?;
+    // waiting fills in the year and day from the next puzzle to unlock rather than the last one
+    let (year, day) = match scaffold.wait {
+        true => calendar::resolve_upcoming(challenge.year, challenge.day, now)?,
+        false => calendar::resolve(challenge.year, challenge.day, now)?,
+    };
+    if scaffold.wait && !calendar::is_unlocked(year, day, now) {
+        wait_for_unlock(year, day).await;
     }
 
-    // Append to mod.rs
-    let mut mod_file = fs::OpenOptions::new().append(true).open(&mod_file_path)?;
-    writeln!(mod_file, "pub mod day{};", day_str)?;
+    let changes = scaffold::create_challenge_files(&client, root, year, day, &options).await?;
+    if options.dry_run {
+        println!("Dry run, no files were changed");
+    }
+    for change in changes {
+        println!("{}", change);
+    }
 
     Ok(())
 }
 
-async fn fetch_challenge_input(year: &str, day: i32) -> Result<String> {
-    let cookie = read_cookie()?;
-    fetch_input_data(str::trim(&cookie), year, day).await
-}
-
-fn read_cookie() -> Result<String> {
-    let home_dir = dirs::home_dir().context("Failed to determine home directory")?;
-    let cookie_file_path = home_dir.join(".aoc_cookie");
-
-    fs::read_to_string(cookie_file_path).context("Failed to read from ~/.aoc_cookie")
-}
-
-async fn fetch_input_data(cookie: &str, year: &str, day: i32) -> Result<String> {
-    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-    let mut request_headers = HeaderMap::new();
-    request_headers.insert(COOKIE, HeaderValue::from_str(cookie)?);
-    let client = reqwest::Client::builder()
-        .default_headers(request_headers)
-        .build()?;
-    let res = client.get(url).send().await?;
-
-    res.text()
-        .await
-        .context("Could not complete request to fetch input data")
-}
-
-fn day_template(year: &str, day: &str) -> String {
-    format!(
-        r#"// {link}
-
-pub fn part1(input: Vec<String>) -> i32 {{
-    0
-}}
-
-pub fn part2(input: Vec<String>) -> i32 {{
-    0
-}}
-
-#[cfg(test)]
-mod tests {{
-    use super::*;
-    use crate::tests::parse_input;
-
-    #[test]
-    fn part1_sample_test() {{
-        let input = parse_input("aoc{year}/res/day{day}_sample.txt");
-        assert_eq!(part1(input), 0);
-    }}
-
-    #[test]
-    fn part1_test() {{
-        let input = parse_input("aoc{year}/res/day{day}.txt");
-        assert_eq!(part1(input), 0);
-    }}
-
-    #[test]
-    fn part2_sample_test() {{
-        let input = parse_input("aoc{year}/res/day{day}_sample.txt");
-        assert_eq!(part2(input), 0);
-    }}
-
-    #[test]
-    fn part2_test() {{
-        let input = parse_input("aoc{year}/res/day{day}.txt");
-        assert_eq!(part2(input), 0);
-    }}
-}}
-"#,
-        link = format!(
-            "https://adventofcode.com/{}/day/{}",
+async fn create_days(
+    client: &AocClient,
+    root: &Path,
+    year: i32,
+    days: &[i32],
+    options: &scaffold::Options,
+) -> Result<()> {
+    let batch = scaffold::create_days(client, root, year, days, options).await?;
+    if options.dry_run {
+        println!("Dry run, no files were changed");
+    }
+    for report in &batch.days {
+        for change in report.result.iter().flatten() {
+            println!("{}", change);
+        }
+    }
+    for change in &batch.modules {
+        println!("{}", change);
+    }
+
+    println!("\n{} summary", year);
+    for report in &batch.days {
+        match &report.result {
+            Err(error) => println!("  day {:>2}  failed: {:#}", report.day, error),
+            Ok(_) => println!("  day {:>2}  {}", report.day, report.outcome()),
+        }
+    }
+
+    let count = |outcome| {
+        batch
+            .days
+            .iter()
+            .filter(|report| report.outcome() == outcome)
+            .count()
+    };
+    let failed = count(Outcome::Failed);
+    println!(
+        "{} succeeded, {} skipped, {} failed",
+        count(Outcome::Succeeded),
+        count(Outcome::Skipped),
+        failed
+    );
+
+    if failed > 0 {
+        bail!("{} of {} days failed", failed, batch.days.len());
+    }
+
+    Ok(())
+}
+
+struct BenchOptions {
+    iterations: usize,
+    sort: SortBy,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    save: bool,
+}
+
+fn bench(
+    root: &Path,
+    inputs: &InputStore,
+    year: i32,
+    days: &[i32],
+    options: BenchOptions,
+) -> Result<()> {
+    if days.is_empty() {
+        bail!("No solvers are registered for {}", year);
+    }
+
+    let path = options
+        .baseline
+        .unwrap_or_else(|| bench::baseline_path(root, year));
+    let baseline = bench::Results::load(&path)?;
+    let results = bench::bench(root, inputs, year, days, options.iterations)?;
+
+    print!(
+        "{}",
+        bench::render(&results, baseline.as_ref(), options.sort, options.threshold)
+    );
+    if options.save && !results.parts.is_empty() {
+        results.save(&path)?;
+        println!("Saved the baseline to {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn import(root: &Path, inputs: &InputStore, year: Option<i32>) -> Result<()> {
+    let imports = inputs.import(root, year)?;
+    if imports.is_empty() {
+        println!("No inputs to import");
+        return Ok(());
+    }
+
+    for import in &imports {
+        println!(
+            "{:>12} {} -> {}",
+            import.result.to_string(),
+            import.from.display(),
+            import.to.display()
+        );
+    }
+
+    let conflicts = imports
+        .iter()
+        .filter(|import| import.result == Imported::Conflict)
+        .count();
+    if conflicts > 0 {
+        bail!(
+            "{} inputs differ from what's already stored and were left in place",
+            conflicts
+        );
+    }
+
+    Ok(())
+}
+
+async fn leaderboard(
+    client_args: &ClientArgs,
+    id: Option<u64>,
+    year: Option<i32>,
+    file: Option<PathBuf>,
+) -> Result<()> {
+    let json = match (file, id) {
+        (Some(file), _) => fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read from {}", file.display()))?,
+        (None, Some(id)) => {
+            let year = year.unwrap_or(calendar::latest_unlocked(Utc::now()).0);
+            build_client(client_args, true, None)?
+                .fetch_leaderboard(year, id)
+                .await?
+        }
+        (None, None) => bail!("Pass a leaderboard --id or a --file to read it from"),
+    };
+
+    print!("{}", Leaderboard::parse(&json)?.render());
+
+    Ok(())
+}
+
+async fn auth(client_args: &ClientArgs, action: AuthAction) -> Result<()> {
+    let profile = client_args.profile.as_deref();
+
+    match action {
+        AuthAction::Set { token } => {
+            let session = Session::parse(&token, "the command line")?;
+            let path = SessionStore::home()?.save(profile, &session)?;
+            println!("Stored {} in {}", session.masked(), path.display());
+        }
+        AuthAction::Show => {
+            let session = session::resolve(profile)?;
+            println!("{} (from {})", session.masked(), session.source);
+        }
+        AuthAction::Check => {
+            let client = build_client(client_args, true, None)?;
+            match session::parse_user(&client.fetch_home_page().await?) {
+                Some(user) => println!("Logged in as {}", user),
+                None => {
+                    bail!("The session token was not accepted, store a fresh one with `auth set`")
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn wait_for_unlock(year: i32, day: i32) {
+    let unlock = calendar::unlock_time(year, day);
+
+    loop {
+        let remaining = (unlock - Utc::now()).num_seconds();
+        if remaining <= 0 {
+            break;
+        }
+
+        print!(
+            "\r{} day {} unlocks in {:02}:{:02}:{:02} ",
             year,
-            day.parse::<i32>().unwrap().to_string()
-        ),
-        year = year,
-        day = day
-    )
+            day,
+            remaining / 3600,
+            remaining / 60 % 60,
+            remaining % 60
+        );
+        io::stdout().flush().ok();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    println!("\r{} day {} is unlocked{}", year, day, " ".repeat(16));
+}
+
+/// `inputs` is where fetched inputs end up, `None` for commands that never fetch any. Only
+/// inputs for the default store are cached as well, since any other may be encrypted.
+fn build_client(
+    args: &ClientArgs,
+    authenticated: bool,
+    inputs: Option<&InputStore>,
+) -> Result<AocClient> {
+    let cookie = if authenticated {
+        Some(session::resolve(args.profile.as_deref())?.cookie())
+    } else {
+        None
+    };
+    // every account has its own inputs, so each profile gets a cache of its own
+    let cache_dir = args
+        .cache_dir
+        .clone()
+        .or_else(|| dirs::cache_dir().map(|dir| dir.join("aoc-rust")))
+        .map(|dir| match &args.profile {
+            Some(profile) => dir.join("profiles").join(profile),
+            None => dir,
+        });
+    let options = ClientOptions {
+        user_agent: args.user_agent.clone(),
+        cache_dir,
+        cache_inputs: inputs.is_some_and(InputStore::is_in_crate),
+        min_interval: Duration::from_millis(args.min_interval_ms),
+    };
+
+    AocClient::new(&args.base_url, cookie.as_deref(), &options)
 }
@@ -0,0 +1,59 @@
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The crate root, which every puzzle and source path is relative to: `root` when given,
+/// otherwise the nearest directory at or above the current one holding a `Cargo.toml`.
+pub fn resolve_root(root: Option<&Path>) -> Result<PathBuf> {
+    match root {
+        Some(root) if root.join("Cargo.toml").is_file() => Ok(root.to_path_buf()),
+        Some(root) => bail!(
+            "{} has no Cargo.toml, it isn't a crate root",
+            root.display()
+        ),
+        None => {
+            let current_dir =
+                env::current_dir().context("Failed to determine current directory")?;
+            find_root(&current_dir)
+        }
+    }
+}
+
+pub fn find_root(start: &Path) -> Result<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .with_context(|| {
+            format!(
+                "Could not find a Cargo.toml in {} or any of its parents, pass --root",
+                start.display()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn find_root_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src/aoc2023/res");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+
+        assert_eq!(find_root(&nested).unwrap(), dir.path());
+        assert_eq!(find_root(dir.path()).unwrap(), dir.path());
+    }
+
+    #[test]
+    fn resolve_root_test() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(resolve_root(Some(dir.path())).is_err());
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        assert_eq!(resolve_root(Some(dir.path())).unwrap(), dir.path());
+    }
+}
@@ -0,0 +1,295 @@
+use crate::client;
+use crate::client::AocClient;
+use anyhow::{bail, Result};
+use scraper::{ElementRef, Html, Node, Selector};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Why a day's input isn't there, for whatever skips the day rather than failing without it.
+pub fn missing_input(year: i32, day: i32) -> String {
+    format!(
+        "no input for {} day {}, inputs aren't published so fetch yours with `aoc-rust -y {} -d {}`",
+        year, day, year, day
+    )
+}
+
+// puzzle paths are relative to the crate root, see `project::resolve_root`, inputs are kept
+// wherever the `store::InputStore` says
+pub fn description_path(year: i32, day: i32) -> PathBuf {
+    PathBuf::from(format!("src/aoc{}/res/day{:02}.md", year, day))
+}
+
+/// Sample files follow the `day08_sample.txt`, `day08_sample2.txt` naming, counting from 1.
+pub fn sample_path(year: i32, day: i32, number: usize) -> PathBuf {
+    let suffix = if number > 1 {
+        number.to_string()
+    } else {
+        String::new()
+    };
+
+    PathBuf::from(format!(
+        "src/aoc{}/res/day{:02}_sample{}.txt",
+        year, day, suffix
+    ))
+}
+
+pub struct Examples {
+    /// Example inputs, most likely sample first
+    pub samples: Vec<String>,
+    /// Answer the description gives for the part 1 example, if it's numeric
+    pub part1_answer: Option<String>,
+}
+
+/// Fetches the puzzle page and writes its description as Markdown, part 2 is only included
+/// once part 1 has been solved.
+pub async fn save_description(
+    client: &AocClient,
+    root: &Path,
+    year: i32,
+    day: i32,
+) -> Result<PathBuf> {
+    let html = client.fetch_puzzle_page(year, day).await?;
+
+    write_description(root, year, day, &html)
+}
+
+pub fn write_description(root: &Path, year: i32, day: i32, html: &str) -> Result<PathBuf> {
+    let path = root.join(description_path(year, day));
+    fs::write(&path, to_markdown(html)?)?;
+
+    Ok(path)
+}
+
+/// Pulls the `<pre><code>` blocks out of the part 1 description. Blocks introduced by a
+/// paragraph mentioning an example are preferred, then larger blocks.
+pub fn examples(html: &str) -> Examples {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc").unwrap();
+    let Some(article) = document.select(&selector).next() else {
+        return Examples {
+            samples: vec![],
+            part1_answer: None,
+        };
+    };
+
+    let mut introduced = false;
+    let mut blocks = vec![];
+    for child in article.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "pre" => {
+                let text = child.text().collect::<String>();
+                if !text.trim().is_empty() && !blocks.iter().any(|(_, block)| block == &text) {
+                    blocks.push((introduced, text));
+                }
+                introduced = false;
+            }
+            _ => {
+                let text = child.text().collect::<String>().to_lowercase();
+                introduced = text.contains("example");
+            }
+        }
+    }
+
+    // the remaining blocks stay in page order
+    if let Some(best) = blocks
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, (introduced, text))| {
+            (*introduced, text.lines().count(), usize::MAX - index)
+        })
+        .map(|(index, _)| index)
+    {
+        let block = blocks.remove(best);
+        blocks.insert(0, block);
+    }
+
+    let answer_selector = Selector::parse("code > em").unwrap();
+    let part1_answer = article
+        .select(&answer_selector)
+        .last()
+        .map(|element| element.text().collect::<String>())
+        .filter(|answer| answer.parse::<i64>().is_ok());
+
+    Examples {
+        samples: blocks.into_iter().map(|(_, text)| text).collect(),
+        part1_answer,
+    }
+}
+
+pub fn to_markdown(html: &str) -> Result<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc").unwrap();
+    let articles: Vec<_> = document.select(&selector).map(blocks).collect();
+
+    if articles.is_empty() {
+        bail!("Could not find a puzzle description in the page");
+    }
+
+    Ok(articles.join("\n"))
+}
+
+fn blocks(element: ElementRef) -> String {
+    let mut markdown = String::new();
+
+    for child in element.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "h2" => markdown.push_str(&format!("## {}\n\n", inline(child).trim())),
+            "pre" => markdown.push_str(&format!(
+                "```\n{}\n```\n\n",
+                child.text().collect::<String>().trim_end_matches('\n')
+            )),
+            "ul" | "ol" => {
+                for item in child.children().filter_map(ElementRef::wrap) {
+                    markdown.push_str(&format!("- {}\n", inline(item).trim()));
+                }
+                markdown.push('\n');
+            }
+            _ => markdown.push_str(&format!("{}\n\n", inline(child).trim())),
+        }
+    }
+
+    markdown
+}
+
+fn inline(element: ElementRef) -> String {
+    element
+        .children()
+        .map(|node| match node.value() {
+            Node::Text(text) => text.to_string(),
+            Node::Element(value) => {
+                let child = ElementRef::wrap(node).unwrap();
+                match value.name() {
+                    "code" => code(child),
+                    "em" => format!("*{}*", inline(child)),
+                    "a" => format!("[{}]({})", inline(child), link(value.attr("href"))),
+                    _ => inline(child),
+                }
+            }
+            _ => String::new(),
+        })
+        .collect()
+}
+
+// highlighted answers are written as `<code><em>35</em></code>`, which can't nest in Markdown
+fn code(element: ElementRef) -> String {
+    let text = format!("`{}`", element.text().collect::<String>());
+    let emphasized = element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .any(|child| child.value().name() == "em");
+
+    if emphasized {
+        format!("*{}*", text)
+    } else {
+        text
+    }
+}
+
+fn link(href: Option<&str>) -> String {
+    match href {
+        Some(href) if href.starts_with('/') => format!("{}{}", client::BASE_URL, href),
+        Some(href) => href.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 6: Wait For It ---</h2><p>The <em>ferry</em> leaves from <a href="/2023/day/5">the island</a>.</p>
+<pre><code>Time:      7  15   30
+Distance:  9  40  200
+</code></pre>
+<p>Multiply them together to get <code><em>288</em></code>.</p>
+<ul><li>The first race lasts <code>7</code> milliseconds.</li><li>The second race is longer.</li></ul>
+</article>
+<p>Your puzzle answer was <code>2344708</code>.</p>
+</main></body></html>"#;
+
+    #[test]
+    fn to_markdown_test() {
+        let expected = r#"## --- Day 6: Wait For It ---
+
+The *ferry* leaves from [the island](https://adventofcode.com/2023/day/5).
+
+```
+Time:      7  15   30
+Distance:  9  40  200
+```
+
+Multiply them together to get *`288`*.
+
+- The first race lasts `7` milliseconds.
+- The second race is longer.
+
+"#;
+
+        assert_eq!(to_markdown(PAGE).unwrap(), expected);
+    }
+
+    #[test]
+    fn to_markdown_part_two_test() {
+        let page = PAGE.replace(
+            "</article>\n",
+            "</article>\n<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Just one race.</p></article>\n",
+        );
+        let markdown = to_markdown(&page).unwrap();
+
+        assert!(markdown.contains("\n## --- Part Two ---\n\nJust one race.\n"));
+        assert!(!markdown.contains("2344708"));
+    }
+
+    #[test]
+    fn examples_test() {
+        let page = r#"<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2>
+<p>It seems like you're meant to use the <code>L</code>eft/<code>R</code>ight instructions:</p>
+<pre><code>RL</code></pre>
+<p>For example:</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+</code></pre>
+<p>Here's another example:</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)
+</code></pre>
+<p>Starting at <code>AAA</code>, follow the left/right instructions. <em>How many steps are required to reach <code>ZZZ</code>?</em> Here, <code><em>6</em></code> steps.</p>
+</article>"#;
+        let examples = examples(page);
+
+        assert_eq!(examples.samples.len(), 3);
+        assert!(examples.samples[0].starts_with("RL\n\nAAA = (BBB, CCC)"));
+        assert_eq!(examples.samples[1], "RL");
+        assert!(examples.samples[2].starts_with("LLR"));
+        assert_eq!(examples.part1_answer, Some("6".to_string()));
+    }
+
+    #[test]
+    fn examples_missing_test() {
+        let examples = examples("<html><body>Please log in.</body></html>");
+
+        assert!(examples.samples.is_empty());
+        assert_eq!(examples.part1_answer, None);
+    }
+
+    #[test]
+    fn sample_path_test() {
+        assert_eq!(
+            sample_path(2023, 8, 1),
+            PathBuf::from("src/aoc2023/res/day08_sample.txt")
+        );
+        assert_eq!(
+            sample_path(2023, 8, 3),
+            PathBuf::from("src/aoc2023/res/day08_sample3.txt")
+        );
+    }
+
+    #[test]
+    fn to_markdown_missing_test() {
+        assert!(to_markdown("<html><body>Please log in.</body></html>").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::runner::{self, Solver};
+use crate::solution::{Param, Solution};
+
+/// A day with a `Solution`, found by the build script in `src/aoc<year>/day<NN>.rs`.
+#[derive(Debug, Clone, Copy)]
+pub struct Registered {
+    pub year: i32,
+    pub day: i32,
+    pub parts: &'static [i32],
+    /// What the day's `Solution` takes as `--param`
+    pub params: &'static [Param],
+    /// The puzzle link from the comment the day's file starts with, if it has one
+    pub link: Option<&'static str>,
+    /// Relative to the crate root
+    pub source: &'static str,
+    pub solver: Solver,
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Every registered day, ordered by year and day.
+pub fn registry() -> &'static [Registered] {
+    REGISTRY
+}
+
+pub fn find(year: i32, day: i32) -> Option<&'static Registered> {
+    REGISTRY
+        .iter()
+        .find(|registered| registered.year == year && registered.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_test() {
+        let registry = registry();
+        assert!(registry
+            .windows(2)
+            .all(|pair| (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day)));
+
+        let day05 = find(2023, 5).unwrap();
+        assert_eq!(day05.parts, &[1, 2]);
+        assert_eq!(day05.link, Some("https://adventofcode.com/2023/day/5"));
+        assert_eq!(day05.source, "src/aoc2023/day05.rs");
+        assert!(day05.params.is_empty());
+        assert_eq!(find(2023, 11).unwrap().params[0].name, "increase_by");
+
+        assert!(find(2022, 1).is_some_and(|day01| day01.link.is_none()));
+        assert!(find(2023, 25).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::answer::Answer;
+use crate::input::{Input, ParseError};
+use crate::solution::{Params, Solution};
+use crate::store::InputStore;
+use crate::{puzzle, registry};
+use anyhow::{anyhow, bail, Result};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub struct Solved {
+    pub part: i32,
+    pub answer: Answer,
+    /// Time spent turning the input into what the parts take, which is shared by every part
+    /// solved from the same parse
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Parses a day's raw input once and solves each of the given parts from it, with the given
+/// parameters overriding the day's defaults.
+pub type Solver = fn(&str, &[i32], &Params) -> Result<Vec<Solved>>;
+
+pub fn run(
+    root: &Path,
+    inputs: &InputStore,
+    year: i32,
+    day: i32,
+    parts: &[i32],
+    params: &Params,
+) -> Result<Vec<Solved>> {
+    let Some(input) = inputs.read(root, year, day)? else {
+        bail!("{}", puzzle::missing_input(year, day));
+    };
+
+    solve_parts(year, day, parts, &input, params)
+}
+
+pub fn solve(year: i32, day: i32, part: i32, input: &str, params: &Params) -> Result<Solved> {
+    let mut solved = solve_parts(year, day, &[part], input, params)?;
+
+    Ok(solved.remove(0))
+}
+
+pub fn solve_parts(
+    year: i32,
+    day: i32,
+    parts: &[i32],
+    input: &str,
+    params: &Params,
+) -> Result<Vec<Solved>> {
+    match solver(year, day) {
+        Some(solver) => solver(input, parts, params),
+        None => bail!("No solver registered for {} day {}", year, day),
+    }
+}
+
+/// The days of `year` with a registered solver.
+pub fn registered_days(year: i32) -> Vec<i32> {
+    registry::registry()
+        .iter()
+        .filter(|registered| registered.year == year)
+        .map(|registered| registered.day)
+        .collect()
+}
+
+pub fn solver(year: i32, day: i32) -> Option<Solver> {
+    registry::find(year, day).map(|registered| registered.solver)
+}
+
+/// The `Solver` for `S`, which is what the registry holds for each day.
+pub fn time<S: Solution>(input: &str, parts: &[i32], params: &Params) -> Result<Vec<Solved>> {
+    let params = params.resolve(S::PARAMS)?;
+    let start = Instant::now();
+    let parsed = S::parse(&Input::new(input)).map_err(|error| {
+        // show where in the input parsing failed rather than only saying so
+        match error.downcast_ref::<ParseError>() {
+            Some(parse_error) => anyhow!("{}", parse_error.render()),
+            None => error,
+        }
+    })?;
+    let parse_time = start.elapsed();
+
+    let solved = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1_with(&parsed, &params).into(),
+                _ => S::part2_with(&parsed, &params).into(),
+            };
+
+            Solved {
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(solved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_input;
+
+    #[test]
+    fn solve_sample_test() {
+        let input = get_input("aoc2023/res/day05_sample.txt");
+        assert_eq!(
+            solve(2023, 5, 2, &input, &Params::default())
+                .unwrap()
+                .answer,
+            Answer::from(46)
+        );
+    }
+
+    #[test]
+    fn solve_blank_line_groups_test() {
+        let input = get_input("aoc2022/res/day01_sample.txt");
+        assert_eq!(
+            solve(2022, 1, 1, &input, &Params::default())
+                .unwrap()
+                .answer,
+            Answer::from(24000)
+        );
+    }
+
+    #[test]
+    fn solve_parts_test() {
+        let input = get_input("aoc2023/res/day05_sample.txt");
+        let solved = solve_parts(2023, 5, &[1, 2], &input, &Params::default()).unwrap();
+        let answers: Vec<_> = solved
+            .iter()
+            .map(|solved| solved.answer.to_string())
+            .collect();
+
+        assert_eq!(answers, vec!["35", "46"]);
+        assert_eq!(solved[0].parse_time, solved[1].parse_time);
+    }
+
+    #[test]
+    fn solve_unregistered_test() {
+        assert!(solve(2023, 25, 1, "", &Params::default()).is_err());
+    }
+
+    #[test]
+    fn solve_with_params_test() {
+        let input = get_input("aoc2023/res/day11_sample.txt");
+        let params = "increase_by=100".parse().unwrap();
+        assert_eq!(
+            solve(2023, 11, 2, &input, &params).unwrap().answer,
+            Answer::from(8410)
+        );
+
+        let params = "steps=6".parse().unwrap();
+        assert!(solve(2023, 11, 2, &input, &params).is_err());
+    }
+
+    #[test]
+    fn solve_malformed_test() {
+        let error = solve(2023, 7, 1, "32T3K 765\nT5X5J 684", &Params::default())
+            .err()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            error,
+            "Unknown card at line 2, column 3\n  |\n2 | T5X5J 684\n  |   ^"
+        );
+
+        let error = solve(2022, 1, 1, "1000\n2000\n\n12a", &Params::default())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.starts_with("Could not parse \"12a\""));
+        assert!(error.ends_with("4 | 12a\n  | ^^^"));
+
+        let malformed = [
+            (
+                2023,
+                10,
+                ".|.\n-L-",
+                "Expected a starting tile S at line 2, column 4",
+            ),
+            (
+                2023,
+                5,
+                "seeds:\n\nseed-to-soil map:\n1 2 3",
+                "Expected at least one seed",
+            ),
+            (
+                2023,
+                3,
+                "",
+                "Expected the engine schematic at line 1, column 1",
+            ),
+            (
+                2022,
+                1,
+                "",
+                "Expected at least one elf's calories at line 1, column 1",
+            ),
+        ];
+        for (year, day, input, expected) in malformed {
+            let error = solve(year, day, 1, input, &Params::default())
+                .err()
+                .unwrap()
+                .to_string();
+            assert!(error.starts_with(expected), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,480 @@
+use crate::answer::Answer;
+use crate::answers::{self, Answers, ANSWERS_FILE};
+use crate::calendar;
+use crate::client::AocClient;
+use crate::puzzle;
+use crate::solution::Params;
+use crate::store::InputStore;
+use crate::template::{Placeholders, Template};
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use parse_display::Display;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum Action {
+    Create,
+    Overwrite,
+    Update,
+    Skip,
+}
+
+pub struct Change {
+    pub action: Action,
+    pub path: PathBuf,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>9} {}", self.action.to_string(), self.path.display())
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    /// Overwrite the day file, input and samples even if they already exist
+    pub force: bool,
+    /// Report the changes without touching any files, only fetching the puzzle page to see which
+    /// samples it has
+    pub dry_run: bool,
+    /// What the day file is generated from
+    pub template: Template,
+    /// Return type of both parts in the generated day file
+    pub answer_type: String,
+    /// Where the fetched input is kept
+    pub inputs: InputStore,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            force: false,
+            dry_run: false,
+            template: Template::default(),
+            answer_type: "i32".to_string(),
+            inputs: InputStore::default(),
+        }
+    }
+}
+
+/// How scaffolding one day of a batch went.
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum Outcome {
+    Succeeded,
+    Skipped,
+    Failed,
+}
+
+pub struct DayReport {
+    pub day: i32,
+    pub result: Result<Vec<Change>>,
+}
+
+impl DayReport {
+    /// A day counts as skipped when its day file was already there.
+    pub fn outcome(&self) -> Outcome {
+        match &self.result {
+            Err(_) => Outcome::Failed,
+            // the day file is always the last change for a day
+            Ok(changes) if changes.last().map(|change| change.action) == Some(Action::Skip) => {
+                Outcome::Skipped
+            }
+            Ok(_) => Outcome::Succeeded,
+        }
+    }
+}
+
+pub struct Batch {
+    pub days: Vec<DayReport>,
+    /// The `mod.rs` and `lib.rs` registrations, made once for every day that didn't fail
+    pub modules: Vec<Change>,
+}
+
+/// Creates the files for a day under the crate `root`, leaving anything that already exists
+/// alone unless forced. Returns every file that was, or with `dry_run` would be, touched, with
+/// paths relative to `root`.
+pub async fn create_challenge_files(
+    client: &AocClient,
+    root: &Path,
+    year: i32,
+    day: i32,
+    options: &Options,
+) -> Result<Vec<Change>> {
+    let mut changes = create_day_files(client, root, year, day, options).await?;
+    changes.extend(register_modules(root, year, &[day], options.dry_run)?);
+
+    Ok(changes)
+}
+
+/// Scaffolds each of `days` in turn, carrying on past days that fail. Days that haven't
+/// unlocked yet fail without a request being made for them.
+pub async fn create_days(
+    client: &AocClient,
+    root: &Path,
+    year: i32,
+    days: &[i32],
+    options: &Options,
+) -> Result<Batch> {
+    let mut reports = vec![];
+    for &day in days {
+        let result = if calendar::is_unlocked(year, day, Utc::now()) {
+            create_day_files(client, root, year, day, options).await
+        } else {
+            Err(anyhow!("{} day {} isn't unlocked yet", year, day))
+        };
+        reports.push(DayReport { day, result });
+    }
+
+    let scaffolded: Vec<_> = reports
+        .iter()
+        .filter(|report| report.result.is_ok())
+        .map(|report| report.day)
+        .collect();
+    let modules = if scaffolded.is_empty() {
+        vec![]
+    } else {
+        register_modules(root, year, &scaffolded, options.dry_run)?
+    };
+
+    Ok(Batch {
+        days: reports,
+        modules,
+    })
+}
+
+async fn create_day_files(
+    client: &AocClient,
+    root: &Path,
+    year: i32,
+    day: i32,
+    options: &Options,
+) -> Result<Vec<Change>> {
+    // File paths
+    let dir_path = PathBuf::from(format!("src/aoc{}", year));
+    let day_file_path = dir_path.join(format!("day{:02}.rs", day));
+    let input_file_path = options.inputs.path(root, year, day);
+    let description_path = puzzle::description_path(year, day);
+
+    let day_action = file_action(&root.join(&day_file_path), options.force);
+    let input_action = file_action(&input_file_path, options.force);
+    let description_action = if root.join(&description_path).exists() {
+        Action::Update
+    } else {
+        Action::Create
+    };
+
+    let mut changes = vec![
+        Change {
+            action: input_action,
+            // the store may well be outside the crate
+            path: input_file_path
+                .strip_prefix(root)
+                .unwrap_or(&input_file_path)
+                .to_path_buf(),
+        },
+        Change {
+            action: description_action,
+            path: description_path,
+        },
+    ];
+
+    if !options.dry_run {
+        // Create directories if they don't exist
+        fs::create_dir_all(root.join(&dir_path).join("res"))?;
+
+        // Store the input, only fetching it when it's missing
+        if input_action != Action::Skip {
+            let input = client.fetch_input_data(year, day).await?;
+            options
+                .inputs
+                .write(root, year, day, &format!("{}\n", input))?;
+        }
+    }
+
+    // Save the puzzle description and write its examples to the sample files. A dry run fetches
+    // the page too, since which samples and answers get written depends on its examples.
+    let page = client.fetch_puzzle_page(year, day).await?;
+    if !options.dry_run {
+        puzzle::write_description(root, year, day, &page)?;
+    }
+
+    let examples = puzzle::examples(&page);
+    changes.extend(write_samples(root, year, day, &examples.samples, options)?);
+    if let Some(answer) = &examples.part1_answer {
+        changes.extend(record_sample_answer(
+            root,
+            year,
+            day,
+            answer,
+            options.dry_run,
+        )?);
+    }
+
+    // Write to day.rs file
+    if day_action != Action::Skip && !options.dry_run {
+        let part1_sample = examples.part1_answer.unwrap_or_else(|| "0".to_string());
+        let contents = options.template.render(&Placeholders {
+            year,
+            day,
+            answer_type: &options.answer_type,
+            part1_sample: &part1_sample,
+        })?;
+        fs::write(root.join(&day_file_path), contents)?;
+    }
+    changes.push(Change {
+        action: day_action,
+        path: day_file_path,
+    });
+
+    Ok(changes)
+}
+
+// the answer the description gives for the first sample, so the answers harness checks part 1
+// against it straight away
+fn record_sample_answer(
+    root: &Path,
+    year: i32,
+    day: i32,
+    answer: &str,
+    dry_run: bool,
+) -> Result<Option<Change>> {
+    let path = Answers::path(root);
+    let mut answers = Answers::load(&path)?;
+    let sample = answers::sample_file(day);
+    if answers
+        .get(year, day, &sample, &Params::default(), 1)
+        .is_some()
+    {
+        return Ok(None);
+    }
+
+    let action = if path.exists() {
+        Action::Update
+    } else {
+        Action::Create
+    };
+    answers.set(
+        year,
+        day,
+        &sample,
+        &Params::default(),
+        1,
+        &Answer::from(answer),
+    );
+    if !dry_run {
+        answers.save(&path)?;
+    }
+
+    Ok(Some(Change {
+        action,
+        path: PathBuf::from(ANSWERS_FILE),
+    }))
+}
+
+// registers the days in mod.rs and, for a new year, the year in lib.rs
+fn register_modules(root: &Path, year: i32, days: &[i32], dry_run: bool) -> Result<Vec<Change>> {
+    let mod_file_path = PathBuf::from(format!("src/aoc{}/mod.rs", year));
+    let day_modules: Vec<_> = days.iter().map(|day| format!("day{:02}", day)).collect();
+    let year_module = format!("aoc{}", year);
+
+    Ok(vec![
+        add_modules(root, &mod_file_path, &day_modules, dry_run)?,
+        add_modules(root, Path::new("src/lib.rs"), &[year_module], dry_run)?,
+    ])
+}
+
+fn write_samples(
+    root: &Path,
+    year: i32,
+    day: i32,
+    samples: &[String],
+    options: &Options,
+) -> Result<Vec<Change>> {
+    // without any examples, fall back to an empty sample file to paste into
+    let samples = if samples.is_empty() {
+        vec![String::new()]
+    } else {
+        samples.to_vec()
+    };
+
+    samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            let path = puzzle::sample_path(year, day, index + 1);
+            let action = match file_action(&root.join(&path), options.force) {
+                // never force a hand pasted sample away when the page had none
+                Action::Overwrite if sample.is_empty() => Action::Skip,
+                action => action,
+            };
+            if action != Action::Skip && !options.dry_run {
+                fs::write(root.join(&path), sample)?;
+            }
+
+            Ok(Change { action, path })
+        })
+        .collect()
+}
+
+// empty files are what a failed or manual scaffold leaves behind, so they're safe to fill
+fn file_action(path: &Path, force: bool) -> Action {
+    let empty = fs::metadata(path).map_or(true, |metadata| metadata.len() == 0);
+
+    if !path.exists() {
+        Action::Create
+    } else if empty {
+        Action::Update
+    } else if force {
+        Action::Overwrite
+    } else {
+        Action::Skip
+    }
+}
+
+fn add_modules(root: &Path, path: &Path, names: &[String], dry_run: bool) -> Result<Change> {
+    let full_path = root.join(path);
+    let contents = if full_path.exists() {
+        fs::read_to_string(&full_path)?
+    } else {
+        String::new()
+    };
+    let new_contents = names
+        .iter()
+        .fold(None, |new_contents: Option<String>, name| {
+            let current = new_contents.as_deref().unwrap_or(&contents);
+            insert_module(current, name).or(new_contents)
+        });
+
+    let action = if !full_path.exists() {
+        Action::Create
+    } else if new_contents.is_none() {
+        Action::Skip
+    } else {
+        Action::Update
+    };
+
+    if let (Some(new_contents), false) = (new_contents, dry_run) {
+        fs::write(&full_path, new_contents)?;
+    }
+
+    Ok(Change {
+        action,
+        path: path.to_path_buf(),
+    })
+}
+
+/// Adds `pub mod <name>;` among the file's plain module declarations, before the first one that
+/// sorts after it. Declarations with attributes, like `#[cfg(test)] pub mod tests;`, and every
+/// other item are left where they are. `None` when the module is already declared.
+fn insert_module(contents: &str, name: &str) -> Option<String> {
+    let mut lines: Vec<_> = contents.lines().collect();
+    if lines.iter().any(|line| declared_module(line) == Some(name)) {
+        return None;
+    }
+
+    let plain: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let attributed = index > 0 && lines[index - 1].trim_start().starts_with("#[");
+            let declared = declared_module(line)?;
+
+            (line.trim_end().ends_with(';') && !attributed).then_some((index, declared))
+        })
+        .collect();
+    let index = plain
+        .iter()
+        .find(|(_, declared)| *declared > name)
+        .map(|(index, _)| *index)
+        .or_else(|| plain.last().map(|(index, _)| index + 1))
+        .unwrap_or(0);
+
+    let declaration = format!("pub mod {};", name);
+    lines.insert(index, &declaration);
+
+    Some(format!("{}\n", lines.join("\n")))
+}
+
+/// The module a `mod` declaration line declares, e.g. `aoc2023` for `pub mod aoc2023;`.
+fn declared_module(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let line = line
+        .strip_prefix("pub(crate) ")
+        .or_else(|| line.strip_prefix("pub "))
+        .unwrap_or(line);
+    let line = line.strip_prefix("mod ")?;
+    let end = line
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(line.len());
+    let (name, rest) = line.split_at(end);
+
+    let valid = !name.is_empty() && rest.trim_start().starts_with([';', '{']);
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_modules_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Path::new("mod.rs");
+        let modules =
+            |names: &[&str]| -> Vec<String> { names.iter().map(|name| name.to_string()).collect() };
+
+        let change = add_modules(dir.path(), path, &modules(&["day02"]), false).unwrap();
+        assert_eq!(change.action, Action::Create);
+        assert_eq!(change.path, path);
+        let change = add_modules(dir.path(), path, &modules(&["day01"]), false).unwrap();
+        assert_eq!(change.action, Action::Update);
+        let change = add_modules(dir.path(), path, &modules(&["day01"]), false).unwrap();
+        assert_eq!(change.action, Action::Skip);
+        let names = modules(&["day04", "day02", "day03"]);
+        let change = add_modules(dir.path(), path, &names, false).unwrap();
+        assert_eq!(change.action, Action::Update);
+
+        let contents = fs::read_to_string(dir.path().join(path)).unwrap();
+        assert_eq!(
+            contents,
+            "pub mod day01;\npub mod day02;\npub mod day03;\npub mod day04;\n"
+        );
+    }
+
+    #[test]
+    fn insert_module_test() {
+        let lib = "\
+//! Solutions
+pub mod aoc2022;
+pub mod aoc2023;
+pub mod shared;
+
+#[cfg(test)]
+pub mod tests;
+";
+
+        assert_eq!(
+            insert_module(lib, "aoc2024").unwrap(),
+            lib.replace("aoc2023;\n", "aoc2023;\npub mod aoc2024;\n")
+        );
+        assert_eq!(
+            insert_module(lib, "zebra").unwrap(),
+            lib.replace("shared;\n", "shared;\npub mod zebra;\n")
+        );
+        assert_eq!(insert_module(lib, "aoc2023"), None);
+        assert_eq!(insert_module(lib, "tests"), None);
+        assert_eq!(
+            insert_module("#[cfg(test)]\nmod tests;\n", "aoc2023").unwrap(),
+            "pub mod aoc2023;\n#[cfg(test)]\nmod tests;\n"
+        );
+        assert_eq!(
+            insert_module("pub mod day03;\npub mod day01;\n", "day02").unwrap(),
+            "pub mod day02;\npub mod day03;\npub mod day01;\n"
+        );
+        assert_eq!(insert_module("pub(crate) mod day01 {}\n", "day01"), None);
+    }
+}
@@ -0,0 +1,238 @@
+use anyhow::{bail, Context, Result};
+use scraper::{Html, Selector};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Overrides any stored token when set.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub struct Session {
+    token: String,
+    /// Where the token was read from, for error messages and `auth show`
+    pub source: String,
+}
+
+impl Session {
+    /// Accepts the bare token or the `session=...` cookie copied from the browser.
+    pub fn parse(raw: &str, source: &str) -> Result<Session> {
+        let token = raw.trim();
+        let token = token.strip_prefix("session=").unwrap_or(token).trim();
+
+        if token.is_empty() {
+            bail!("The session token from {} is empty", source);
+        }
+        if !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(
+                "The session token from {} doesn't look like a token",
+                source
+            );
+        }
+
+        Ok(Session {
+            token: token.to_string(),
+            source: source.to_string(),
+        })
+    }
+
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+
+    pub fn masked(&self) -> String {
+        let chars: Vec<_> = self.token.chars().collect();
+        if chars.len() <= 8 {
+            return "*".repeat(chars.len());
+        }
+
+        let start: String = chars[..4].iter().collect();
+        let end: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}{}{}", start, "*".repeat(chars.len() - 8), end)
+    }
+}
+
+/// Tokens stored as `~/.aoc_cookie`, or `~/.aoc_cookie.<profile>` for named profiles.
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(dir: PathBuf) -> SessionStore {
+        SessionStore { dir }
+    }
+
+    pub fn home() -> Result<SessionStore> {
+        let home_dir = dirs::home_dir().context("Failed to determine home directory")?;
+
+        Ok(SessionStore::new(home_dir))
+    }
+
+    pub fn path(&self, profile: Option<&str>) -> Result<PathBuf> {
+        let path = match profile {
+            Some(profile) => self
+                .dir
+                .join(format!(".aoc_cookie.{}", parse_profile(profile)?)),
+            None => self.dir.join(".aoc_cookie"),
+        };
+
+        Ok(path)
+    }
+
+    pub fn load(&self, profile: Option<&str>) -> Result<Session> {
+        let path = self.path(profile)?;
+        let source = path.display().to_string();
+        let raw = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Failed to read from {}, store a token with `auth set` or set {}",
+                source, SESSION_VAR
+            )
+        })?;
+
+        Session::parse(&raw, &source)
+    }
+
+    /// Only the user can read the stored token, like the input key.
+    pub fn save(&self, profile: Option<&str>, session: &Session) -> Result<PathBuf> {
+        let path = self.path(profile)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&path)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+        // a file that was already there keeps its permissions otherwise
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        writeln!(file, "{}", session.cookie())?;
+
+        Ok(path)
+    }
+}
+
+/// A profile name, which ends up in file names so is limited to letters, digits, `_` and `-`.
+pub fn parse_profile(profile: &str) -> Result<String> {
+    let valid = profile
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if profile.is_empty() || !valid {
+        bail!(
+            "Invalid profile name {:?}, use only letters, digits, _ and -",
+            profile
+        );
+    }
+
+    Ok(profile.to_string())
+}
+
+/// The session to use. A profile always means its stored token, since that names the account
+/// to use. Without one it's `AOC_SESSION` first and then the default stored token.
+pub fn resolve(profile: Option<&str>) -> Result<Session> {
+    match env::var(SESSION_VAR) {
+        Ok(raw) if profile.is_none() && !raw.trim().is_empty() => Session::parse(&raw, SESSION_VAR),
+        _ => SessionStore::home()?.load(profile),
+    }
+}
+
+/// The logged in user's name from the page header, `None` when the session isn't logged in.
+pub fn parse_user(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("header div.user").unwrap();
+    let user = document.select(&selector).next()?;
+
+    user.text()
+        .next()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let bare = Session::parse("53616c7465645f5f\n", "test").unwrap();
+        let cookie = Session::parse("session=53616c7465645f5f", "test").unwrap();
+
+        assert_eq!(bare.cookie(), "session=53616c7465645f5f");
+        assert_eq!(cookie.cookie(), "session=53616c7465645f5f");
+        assert!(Session::parse("session=", "test").is_err());
+        assert!(Session::parse("session=abc; path=/", "test").is_err());
+    }
+
+    #[test]
+    fn masked_test() {
+        let session = Session::parse("53616c7465645f5f", "test").unwrap();
+
+        assert_eq!(session.masked(), "5361********5f5f");
+        assert_eq!(Session::parse("abc", "test").unwrap().masked(), "***");
+    }
+
+    #[test]
+    fn store_profiles_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStore::new(dir.path().to_path_buf());
+        let session = Session::parse("abc123", "test").unwrap();
+        let work = Session::parse("def456", "test").unwrap();
+
+        store.save(None, &session).unwrap();
+        store.save(Some("work"), &work).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".aoc_cookie")).unwrap(),
+            "session=abc123\n"
+        );
+        assert_eq!(store.load(None).unwrap().cookie(), "session=abc123");
+        assert_eq!(store.load(Some("work")).unwrap().cookie(), "session=def456");
+        assert!(store.load(Some("home")).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = dir.path().join(".aoc_cookie");
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            store.save(None, &session).unwrap();
+            for path in [path, dir.path().join(".aoc_cookie.work")] {
+                let mode = fs::metadata(path).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+            }
+        }
+    }
+
+    #[test]
+    fn profile_name_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStore::new(dir.path().to_path_buf());
+        let session = Session::parse("abc123", "test").unwrap();
+
+        assert_eq!(parse_profile("work_2-b").unwrap(), "work_2-b");
+        for profile in ["../../x", "a/b", "work.old", ""] {
+            assert!(parse_profile(profile).is_err(), "{}", profile);
+        }
+        let error = store.save(Some("../../x"), &session).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid profile name \"../../x\", use only letters, digits, _ and -"
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn parse_user_test() {
+        let logged_in = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<div class="user">natebrunette <span class="star-count">22*</span></div></div></header>"#;
+        let logged_out = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<nav><ul><li><a href="/2023/auth/login">[Log In]</a></li></ul></nav></div></header>"#;
+
+        assert_eq!(parse_user(logged_in), Some("natebrunette".to_string()));
+        assert_eq!(parse_user(logged_out), None);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::answer::Answer;
+use crate::input::Input;
+use anyhow::{anyhow, bail, Context, Result};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A day's puzzle. The input is parsed once into `Parsed`, which both parts then share, so the
+/// runner can time parsing on its own and reuse it across parts.
+pub trait Solution {
+    type Parsed;
+    /// Any integer type or `String`, which the runner turns into an `Answer`
+    type Answer: Into<Answer>;
+
+    /// Named constants the parts take, see `part1_with` and `part2_with`
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+
+    fn part1(input: &Self::Parsed) -> Self::Answer;
+
+    fn part2(input: &Self::Parsed) -> Self::Answer;
+
+    /// Part 1 with a value for each of `PARAMS`, which is what the runner calls. Only days with
+    /// parameters need to override it.
+    fn part1_with(input: &Self::Parsed, _params: &Params) -> Self::Answer {
+        Self::part1(input)
+    }
+
+    /// Part 2 with a value for each of `PARAMS`, which is what the runner calls. Only days with
+    /// parameters need to override it.
+    fn part2_with(input: &Self::Parsed, _params: &Params) -> Self::Answer {
+        Self::part2(input)
+    }
+}
+
+/// A constant a day's parts depend on which the puzzle's examples vary, e.g. how far empty space
+/// expands. The real input is solved with `default`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+}
+
+/// Parameter values by name, written as `increase_by=100` with several separated by commas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Params(BTreeMap<String, i64>);
+
+impl Params {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of a parameter the day declares. Values are always resolved against the
+    /// declarations before a part sees them, so any other name is a bug in the day.
+    pub fn get(&self, name: &str) -> i64 {
+        match self.0.get(name) {
+            Some(&value) => value,
+            None => panic!("no value for the parameter {}", name),
+        }
+    }
+
+    /// These values with the default filled in for each of `declared` that isn't set, failing on
+    /// a name that isn't declared.
+    pub fn resolve(&self, declared: &[Param]) -> Result<Params> {
+        if let Some(name) = self
+            .0
+            .keys()
+            .find(|&name| !declared.iter().any(|param| param.name == name))
+        {
+            if declared.is_empty() {
+                bail!("Unknown parameter {}, the day doesn't take any", name);
+            }
+            bail!(
+                "Unknown parameter {}, the day takes {}",
+                name,
+                declared.iter().map(|param| param.name).join(", ")
+            );
+        }
+
+        let resolved = declared
+            .iter()
+            .map(|param| {
+                let value = self.0.get(param.name).copied().unwrap_or(param.default);
+                (param.name.to_string(), value)
+            })
+            .collect();
+
+        Ok(Params(resolved))
+    }
+}
+
+impl FromIterator<(String, i64)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, i64)>>(params: I) -> Self {
+        Params(params.into_iter().collect())
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let params = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .join(",");
+
+        f.write_str(&params)
+    }
+}
+
+impl FromStr for Params {
+    type Err = anyhow::Error;
+
+    fn from_str(params: &str) -> Result<Self> {
+        if params.is_empty() {
+            return Ok(Params::default());
+        }
+
+        params.split(',').map(parse_param).collect()
+    }
+}
+
+/// One `name=value` pair, as given to `--param`.
+pub fn parse_param(param: &str) -> Result<(String, i64)> {
+    let (name, value) = param
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected NAME=VALUE, got {}", param))?;
+    let value = value
+        .trim()
+        .parse()
+        .with_context(|| format!("{} isn't an integer", value))?;
+
+    Ok((name.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "increase_by",
+            default: 1000000,
+        },
+        Param {
+            name: "steps",
+            default: 64,
+        },
+    ];
+
+    #[test]
+    fn resolve_test() {
+        let params: Params = "increase_by=10".parse().unwrap();
+        let resolved = params.resolve(PARAMS).unwrap();
+        assert_eq!(resolved.get("increase_by"), 10);
+        assert_eq!(resolved.get("steps"), 64);
+        assert_eq!(resolved.to_string(), "increase_by=10,steps=64");
+
+        let error = Params::from_iter([parse_param("step=6").unwrap()])
+            .resolve(PARAMS)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown parameter step, the day takes increase_by, steps"
+        );
+        assert!(params.resolve(&[]).is_err());
+    }
+
+    #[test]
+    fn parse_test() {
+        let params: Params = "steps=6,increase_by=-2".parse().unwrap();
+        assert_eq!(params.to_string(), "increase_by=-2,steps=6");
+        assert_eq!("".parse::<Params>().unwrap(), Params::default());
+        assert!("steps".parse::<Params>().is_err());
+        assert!("steps=six".parse::<Params>().is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use parse_display::Display;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const INPUT_KEY_VAR: &str = "AOC_INPUT_KEY";
+
+const NONCE_LEN: usize = 12;
+
+/// Where real puzzle inputs are kept. By default that's `src/aoc<year>/res/day<NN>.txt` in the
+/// crate, which `.gitignore` keeps out of git, so those only ever live in one checkout. To keep
+/// inputs anywhere else they have to be imported into a store outside the crate or an
+/// encrypted one. With `dir` set it's `<dir>/<year>/day<NN>.txt` instead. With `key_file` set
+/// inputs are encrypted with the key in it and get an extra `.enc` extension, so they can be
+/// committed without publishing them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputStore {
+    pub dir: Option<PathBuf>,
+    /// Holds the key as hex, a new key is generated the first time an input is written
+    pub key_file: Option<PathBuf>,
+}
+
+/// What importing an input from the crate's `res` directories did.
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum Imported {
+    Moved,
+    /// The store already had the same input, so only the crate's copy was removed
+    Deduplicated,
+    /// The store already had a different input, both were left alone
+    Conflict,
+}
+
+pub struct Import {
+    pub year: i32,
+    pub day: i32,
+    /// Relative to the crate root
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub result: Imported,
+}
+
+impl InputStore {
+    /// The store configured through `AOC_INPUT_DIR` and `AOC_INPUT_KEY`, which is how the tests
+    /// find inputs since they can't be given the command line options.
+    pub fn from_env() -> InputStore {
+        InputStore {
+            dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            key_file: env::var_os(INPUT_KEY_VAR).map(PathBuf::from),
+        }
+    }
+
+    /// Whether inputs are kept as plain files in the crate, where `import` would leave them.
+    pub fn is_in_crate(&self) -> bool {
+        self.dir.is_none() && self.key_file.is_none()
+    }
+
+    pub fn path(&self, root: &Path, year: i32, day: i32) -> PathBuf {
+        let extension = if self.key_file.is_some() { ".enc" } else { "" };
+        let name = format!("day{:02}.txt{}", day, extension);
+
+        match &self.dir {
+            Some(dir) => dir.join(year.to_string()).join(name),
+            None => root.join(format!("src/aoc{}/res", year)).join(name),
+        }
+    }
+
+    /// `None` when the input hasn't been stored yet.
+    pub fn read(&self, root: &Path, year: i32, day: i32) -> Result<Option<String>> {
+        let path = self.path(root, year, day);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents =
+            fs::read(&path).with_context(|| format!("Could not read {}", path.display()))?;
+        let contents = match &self.key_file {
+            Some(key_file) => decrypt(&load_key(key_file)?, &contents)
+                .with_context(|| format!("Could not decrypt {}", path.display()))?,
+            None => contents,
+        };
+
+        String::from_utf8(contents)
+            .map(Some)
+            .with_context(|| format!("{} isn't valid UTF-8", path.display()))
+    }
+
+    pub fn write(&self, root: &Path, year: i32, day: i32, input: &str) -> Result<PathBuf> {
+        let path = self.path(root, year, day);
+        let contents = match &self.key_file {
+            Some(key_file) => encrypt(&load_or_create_key(key_file)?, input.as_bytes())?,
+            None => input.as_bytes().to_vec(),
+        };
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, contents)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+
+        Ok(path)
+    }
+
+    /// Moves the plain inputs in the crate's `res` directories into the store, for `year` or
+    /// every year.
+    pub fn import(&self, root: &Path, year: Option<i32>) -> Result<Vec<Import>> {
+        if self.is_in_crate() {
+            bail!(
+                "Inputs are already kept in the crate, set {} or {} to import them elsewhere",
+                INPUT_DIR_VAR,
+                INPUT_KEY_VAR
+            );
+        }
+
+        let mut imports = vec![];
+        for (input_year, day) in crate_inputs(root)? {
+            if year.is_some_and(|year| year != input_year) {
+                continue;
+            }
+
+            let from = PathBuf::from(format!("src/aoc{}/res/day{:02}.txt", input_year, day));
+            let input = fs::read_to_string(root.join(&from))?;
+            let result = match self.read(root, input_year, day)? {
+                Some(stored) if stored == input => Imported::Deduplicated,
+                Some(_) => Imported::Conflict,
+                None => {
+                    self.write(root, input_year, day, &input)?;
+                    // only give up the original once the stored copy is known to read back
+                    if self.read(root, input_year, day)?.as_deref() != Some(input.as_str()) {
+                        bail!(
+                            "{} didn't read back the same from the store",
+                            from.display()
+                        );
+                    }
+                    Imported::Moved
+                }
+            };
+            if result != Imported::Conflict {
+                fs::remove_file(root.join(&from))?;
+            }
+
+            imports.push(Import {
+                year: input_year,
+                day,
+                to: self.path(root, input_year, day),
+                from,
+                result,
+            });
+        }
+
+        Ok(imports)
+    }
+}
+
+// every `src/aoc<year>/res/day<NN>.txt` in the crate, ordered by year and day
+fn crate_inputs(root: &Path) -> Result<Vec<(i32, i32)>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(root.join("src"))? {
+        let entry = entry?;
+        let year = entry.file_name().to_str().and_then(|name| {
+            let year = name.strip_prefix("aoc")?;
+            year.parse::<i32>().ok()
+        });
+        let (Some(year), Ok(days)) = (year, fs::read_dir(entry.path().join("res"))) else {
+            continue;
+        };
+
+        for day in days {
+            let name = day?.file_name();
+            let day = name
+                .to_str()
+                .and_then(|name| name.strip_prefix("day")?.strip_suffix(".txt"))
+                .filter(|day| day.len() == 2)
+                .and_then(|day| day.parse::<i32>().ok());
+            if let Some(day) = day {
+                inputs.push((year, day));
+            }
+        }
+    }
+    inputs.sort();
+
+    Ok(inputs)
+}
+
+fn load_key(path: &Path) -> Result<Key> {
+    let hex = fs::read_to_string(path).with_context(|| {
+        format!(
+            "Could not read the input key from {}, copy the key the inputs were encrypted with there",
+            path.display()
+        )
+    })?;
+
+    parse_key(hex.trim()).with_context(|| format!("{} doesn't hold a valid key", path.display()))
+}
+
+fn load_or_create_key(path: &Path) -> Result<Key> {
+    if path.exists() {
+        return load_key(path);
+    }
+
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let hex: String = key.iter().map(|byte| format!("{:02x}", byte)).collect();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", hex))
+        .with_context(|| format!("Could not save a new input key to {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(key)
+}
+
+fn parse_key(hex: &str) -> Result<Key> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        bail!("expected 64 hex digits");
+    }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|start| u8::from_str_radix(&hex[start..start + 2], 16))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(*Key::from_slice(&bytes))
+}
+
+// a fresh nonce per write, stored in front of the ciphertext
+fn encrypt(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow!("encryption failed"))?;
+
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn decrypt(key: &Key, contents: &[u8]) -> Result<Vec<u8>> {
+    if contents.len() < NONCE_LEN {
+        bail!("it's too short to be an encrypted input");
+    }
+
+    let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("it was encrypted with a different key or has been changed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_test() {
+        let root = Path::new("/crate");
+        let store = InputStore::default();
+        assert_eq!(
+            store.path(root, 2023, 5),
+            PathBuf::from("/crate/src/aoc2023/res/day05.txt")
+        );
+
+        let store = InputStore {
+            dir: Some(PathBuf::from("/inputs")),
+            key_file: Some(PathBuf::from("/keys/input.key")),
+        };
+        assert_eq!(
+            store.path(root, 2023, 5),
+            PathBuf::from("/inputs/2023/day05.txt.enc")
+        );
+    }
+
+    #[test]
+    fn encrypted_round_trip_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore {
+            dir: None,
+            key_file: Some(dir.path().join("input.key")),
+        };
+
+        assert_eq!(store.read(dir.path(), 2023, 5).unwrap(), None);
+        let path = store.write(dir.path(), 2023, 5, "seeds: 79 14\n").unwrap();
+        assert!(!String::from_utf8_lossy(&fs::read(&path).unwrap()).contains("seeds"));
+        assert_eq!(
+            store.read(dir.path(), 2023, 5).unwrap().as_deref(),
+            Some("seeds: 79 14\n")
+        );
+
+        // a different key can't read it
+        fs::write(dir.path().join("input.key"), "00".repeat(32)).unwrap();
+        let error = store.read(dir.path(), 2023, 5).unwrap_err();
+        assert!(format!("{:#}", error).contains("different key"));
+    }
+
+    #[test]
+    fn import_test() {
+        let root = tempfile::tempdir().unwrap();
+        let inputs = tempfile::tempdir().unwrap();
+        let res = root.path().join("src/aoc2023/res");
+        fs::create_dir_all(&res).unwrap();
+        for (name, contents) in [
+            ("day01.txt", "1\n"),
+            ("day02.txt", "2\n"),
+            ("day03.txt", "3\n"),
+            ("day01_sample.txt", "sample\n"),
+        ] {
+            fs::write(res.join(name), contents).unwrap();
+        }
+        let store = InputStore {
+            dir: Some(inputs.path().to_path_buf()),
+            key_file: None,
+        };
+        store.write(root.path(), 2023, 2, "2\n").unwrap();
+        store.write(root.path(), 2023, 3, "other\n").unwrap();
+
+        let imports = store.import(root.path(), None).unwrap();
+        let results: Vec<_> = imports.iter().map(|import| import.result).collect();
+        assert_eq!(
+            results,
+            vec![Imported::Moved, Imported::Deduplicated, Imported::Conflict]
+        );
+        assert_eq!(
+            store.read(root.path(), 2023, 1).unwrap().as_deref(),
+            Some("1\n")
+        );
+        assert!(!res.join("day01.txt").exists());
+        assert!(!res.join("day02.txt").exists());
+        assert!(res.join("day03.txt").exists());
+        assert!(res.join("day01_sample.txt").exists());
+
+        assert!(InputStore::default().import(root.path(), None).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use crate::answer::Answer;
+use crate::client::AocClient;
+use anyhow::{bail, Context, Result};
+use chrono::Local;
+use parse_display::{Display, FromStr};
+use std::cmp::Ordering;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Display, FromStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    #[display("rate_limited({0})")]
+    RateLimited(u64),
+}
+
+impl Verdict {
+    pub fn describe(&self) -> String {
+        match self {
+            Verdict::Correct => "correct".to_string(),
+            Verdict::TooHigh => "wrong, too high".to_string(),
+            Verdict::TooLow => "wrong, too low".to_string(),
+            Verdict::Wrong => "wrong".to_string(),
+            Verdict::RateLimited(secs) => format!("rate limited, wait {}s", secs),
+        }
+    }
+}
+
+#[derive(Display, FromStr, Debug, Clone, PartialEq, Eq)]
+#[display("{submitted_at}\t{part}\t{answer}\t{verdict}")]
+pub struct Attempt {
+    pub submitted_at: String,
+    pub part: i32,
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+/// Every answer submitted for a day, one attempt per line.
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Relative to the crate root, like the other puzzle files.
+    pub fn path(year: i32, day: i32) -> PathBuf {
+        PathBuf::from(format!("src/aoc{}/res/day{:02}_submissions.txt", year, day))
+    }
+
+    pub fn load(path: &Path) -> Result<History> {
+        let attempts = if path.exists() {
+            fs::read_to_string(path)?
+                .lines()
+                .filter(|&line| !line.is_empty())
+                .map(|line| {
+                    line.parse::<Attempt>()
+                        .map_err(|_| anyhow::anyhow!("Malformed submission history line: {}", line))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            vec![]
+        };
+
+        Ok(History {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Refuses answers that the history already proves wrong, so they are never sent twice.
+    pub fn check(&self, part: i32, answer: &Answer) -> Result<()> {
+        let attempts = self.attempts.iter().filter(|attempt| attempt.part == part);
+
+        for attempt in attempts {
+            let rejected = match attempt.verdict {
+                Verdict::Correct => true,
+                Verdict::RateLimited(_) => false,
+                Verdict::Wrong => &attempt.answer == answer,
+                Verdict::TooHigh => {
+                    &attempt.answer == answer
+                        || answer.compare(&attempt.answer).is_some_and(Ordering::is_ge)
+                }
+                Verdict::TooLow => {
+                    &attempt.answer == answer
+                        || answer.compare(&attempt.answer).is_some_and(Ordering::is_le)
+                }
+            };
+
+            if rejected {
+                bail!(
+                    "Not submitting {} for part {}, {} was already {}",
+                    answer,
+                    part,
+                    attempt.answer,
+                    attempt.verdict.describe()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, part: i32, answer: &Answer, verdict: Verdict) -> Result<()> {
+        let attempt = Attempt {
+            submitted_at: Local::now().to_rfc3339(),
+            part,
+            answer: answer.clone(),
+            verdict,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+
+        Ok(())
+    }
+}
+
+pub async fn submit(
+    client: &AocClient,
+    history: &mut History,
+    year: i32,
+    day: i32,
+    part: i32,
+    answer: &Answer,
+) -> Result<Verdict> {
+    history.check(part, answer)?;
+    let body = client
+        .submit_answer(year, day, part, &answer.to_string())
+        .await?;
+    let verdict = parse_verdict(&body)?;
+    history.record(part, answer, verdict)?;
+
+    Ok(verdict)
+}
+
+pub fn parse_verdict(body: &str) -> Result<Verdict> {
+    if body.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if body.contains("You gave an answer too recently") {
+        let wait = parse_wait(body).context("Could not read the wait time from the response")?;
+        Ok(Verdict::RateLimited(wait))
+    } else if body.contains("That's not the right answer") {
+        if body.contains("answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if body.contains("answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if body.contains("You don't seem to be solving the right level") {
+        bail!("This part is either already solved or not unlocked yet")
+    } else {
+        bail!("Unrecognized response from the answer endpoint")
+    }
+}
+
+// e.g. "You have 1m 5s left to wait."
+fn parse_wait(body: &str) -> Option<u64> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+
+    body[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (num, unit) = amount.split_at(amount.len() - 1);
+            let num = num.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(num * 60 * 60),
+                "m" => Some(num * 60),
+                "s" => Some(num),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tiny_http::{Response, Server};
+
+    fn article(text: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn parse_verdict_test() {
+        let correct = article("That's the right answer!  You are one gold star closer.");
+        let too_high = article("That's not the right answer; your answer is too high.");
+        let too_low = article("That's not the right answer; your answer is too low.");
+        let wrong = article("That's not the right answer.  If you're stuck, make sure...");
+        let limited = article("You gave an answer too recently.  You have 1m 5s left to wait.");
+
+        assert_eq!(parse_verdict(&correct).unwrap(), Verdict::Correct);
+        assert_eq!(parse_verdict(&too_high).unwrap(), Verdict::TooHigh);
+        assert_eq!(parse_verdict(&too_low).unwrap(), Verdict::TooLow);
+        assert_eq!(parse_verdict(&wrong).unwrap(), Verdict::Wrong);
+        assert_eq!(parse_verdict(&limited).unwrap(), Verdict::RateLimited(65));
+        assert!(parse_verdict(&article("Puzzle inputs differ by user.")).is_err());
+    }
+
+    #[test]
+    fn history_round_trip_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day05_submissions.txt");
+
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(1, &Answer::from(100), Verdict::TooHigh)
+            .unwrap();
+        history
+            .record(1, &Answer::from(50), Verdict::RateLimited(30))
+            .unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.attempts().len(), 2);
+        assert_eq!(history.attempts()[0].verdict, Verdict::TooHigh);
+        assert_eq!(history.attempts()[1].verdict, Verdict::RateLimited(30));
+    }
+
+    #[test]
+    fn history_check_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(&dir.path().join("history.txt")).unwrap();
+        history
+            .record(1, &Answer::from(100), Verdict::TooHigh)
+            .unwrap();
+        history
+            .record(1, &Answer::from(10), Verdict::TooLow)
+            .unwrap();
+        history
+            .record(1, &Answer::from(42), Verdict::Wrong)
+            .unwrap();
+        history
+            .record(1, &Answer::from(50), Verdict::RateLimited(30))
+            .unwrap();
+
+        assert!(history.check(1, &Answer::from(100)).is_err());
+        assert!(history.check(1, &Answer::from(150)).is_err());
+        assert!(history.check(1, &Answer::from(5)).is_err());
+        assert!(history.check(1, &Answer::from(42)).is_err());
+        assert!(history.check(1, &Answer::from(50)).is_ok());
+        assert!(history.check(2, &Answer::from(100)).is_ok());
+
+        history
+            .record(1, &Answer::from(50), Verdict::Correct)
+            .unwrap();
+        assert!(history.check(1, &Answer::from(51)).is_err());
+    }
+
+    #[tokio::test]
+    async fn submit_test() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let handle = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let path = request.url().to_string();
+            let text = article("That's not the right answer; your answer is too low.");
+            request.respond(Response::from_string(text)).unwrap();
+
+            (path, body)
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(&dir.path().join("history.txt")).unwrap();
+        let client = AocClient::new(&base_url, Some("session=abc"), &Default::default()).unwrap();
+        let verdict = submit(&client, &mut history, 2023, 5, 2, &Answer::from(46))
+            .await
+            .unwrap();
+
+        let (path, body) = handle.join().unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+        assert_eq!(path, "/2023/day/5/answer");
+        assert_eq!(body, "level=2&answer=46");
+        assert!(submit(&client, &mut history, 2023, 5, 2, &Answer::from(46))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use anyhow::{bail, Context, Result};
+use parse_display::{Display, FromStr};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The template every built-in style renders, also a starting point for custom ones.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/day.tpl");
+
+/// Project templates live in `templates/<name>.tpl` and win over the user's, which live in
+/// `<config dir>/aoc-rust/templates/<name>.tpl`.
+pub const TEMPLATE_DIR: &str = "templates";
+
+/// A `day.tpl` in either template directory replaces the built-in template for every style,
+/// which is read when the binary is built and so doesn't see later edits to the crate's copy.
+pub const DEFAULT_TEMPLATE_NAME: &str = "day";
+
+/// What a day parses its input into before handing it to both parts.
+#[derive(Display, FromStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[display(style = "lowercase")]
+pub enum InputStyle {
+    /// Every line, `Vec<String>`
+    Lines,
+    /// The whole file, `String`
+    Raw,
+    /// One row of characters per line, `Vec<Vec<char>>`
+    Grid,
+}
+
+impl InputStyle {
+    pub fn input_type(&self) -> &'static str {
+        match self {
+            InputStyle::Lines => "Vec<String>",
+            InputStyle::Raw => "String",
+            InputStyle::Grid => "Vec<Vec<char>>",
+        }
+    }
+
+    /// The body of the generated `Solution::parse`, given `input: &Input`.
+    pub fn parser(&self) -> &'static str {
+        match self {
+            InputStyle::Lines => "Ok(input.lines())",
+            InputStyle::Raw => "Ok(input.raw().to_string())",
+            InputStyle::Grid => "input.grid()",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub source: String,
+    pub style: InputStyle,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Template {
+            name: InputStyle::Lines.to_string(),
+            source: DEFAULT_TEMPLATE.to_string(),
+            style: InputStyle::Lines,
+        }
+    }
+}
+
+/// What gets filled into a template's `{{placeholders}}`.
+pub struct Placeholders<'a> {
+    pub year: i32,
+    pub day: i32,
+    pub answer_type: &'a str,
+    pub part1_sample: &'a str,
+}
+
+impl Template {
+    /// Finds `name` in the project's and then the user's template directory. The `lines`, `raw`
+    /// and `grid` styles then fall back to a `day.tpl` there, and finally to the built-in
+    /// template. A template named after a style renders with that style, any other name gets
+    /// `lines`.
+    pub fn load(name: &str, project_dir: &Path) -> Result<Template> {
+        let builtin = name.parse::<InputStyle>().ok();
+        let style = builtin.unwrap_or(InputStyle::Lines);
+
+        let mut file_names = vec![name];
+        if builtin.is_some() {
+            file_names.push(DEFAULT_TEMPLATE_NAME);
+        }
+        let dirs = template_dirs(project_dir);
+        for file_name in file_names {
+            for dir in &dirs {
+                let path = dir.join(format!("{}.tpl", file_name));
+                if !path.exists() {
+                    continue;
+                }
+                let source = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read template {}", path.display()))?;
+
+                return Ok(Template {
+                    name: name.to_string(),
+                    source,
+                    style,
+                });
+            }
+        }
+
+        if builtin.is_none() {
+            bail!(
+                "No template named {}, add one as {}/{}.tpl or use one of lines, raw or grid",
+                name,
+                TEMPLATE_DIR,
+                name
+            );
+        }
+
+        Ok(Template {
+            name: name.to_string(),
+            source: DEFAULT_TEMPLATE.to_string(),
+            style,
+        })
+    }
+
+    pub fn render(&self, placeholders: &Placeholders) -> Result<String> {
+        let link = format!(
+            "https://adventofcode.com/{}/day/{}",
+            placeholders.year, placeholders.day
+        );
+        let values = [
+            ("year", placeholders.year.to_string()),
+            ("day", format!("{:02}", placeholders.day)),
+            ("day_number", placeholders.day.to_string()),
+            ("link", link),
+            ("input_style", self.style.to_string()),
+            ("input_type", self.style.input_type().to_string()),
+            ("parse_input", self.style.parser().to_string()),
+            ("answer_type", placeholders.answer_type.to_string()),
+            ("part1_sample", placeholders.part1_sample.to_string()),
+        ];
+
+        let mut rendered = String::new();
+        let mut rest = self.source.as_str();
+        while let Some(start) = rest.find("{{") {
+            let (before, after) = rest.split_at(start);
+            rendered.push_str(before);
+
+            let placeholder = after[2..].find("}}").map(|end| &after[2..end + 2]);
+            match placeholder {
+                // anything else with braces, e.g. `format!("{{")`, is left as it is
+                Some(key) if is_placeholder(key) => {
+                    let Some((_, value)) = values.iter().find(|(name, _)| *name == key) else {
+                        bail!(
+                            "Unknown placeholder {{{{{}}}}} in template {}",
+                            key,
+                            self.name
+                        );
+                    };
+                    rendered.push_str(value);
+                    rest = &after[key.len() + 4..];
+                }
+                _ => {
+                    rendered.push_str("{{");
+                    rest = &after[2..];
+                }
+            }
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
+    }
+}
+
+fn is_placeholder(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn template_dirs(project_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_dir.join(TEMPLATE_DIR)];
+    if let Some(config_dir) = dirs::config_dir() {
+        dirs.push(config_dir.join("aoc-rust").join(TEMPLATE_DIR));
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders() -> Placeholders<'static> {
+        Placeholders {
+            year: 2023,
+            day: 5,
+            answer_type: "u64",
+            part1_sample: "35",
+        }
+    }
+
+    #[test]
+    fn render_builtin_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = Template::load("raw", dir.path()).unwrap();
+        let rendered = template.render(&placeholders()).unwrap();
+
+        assert!(rendered.starts_with("// https://adventofcode.com/2023/day/5\n"));
+        assert!(rendered.contains("pub struct Day05;\n"));
+        assert!(rendered.contains("    type Parsed = String;\n    type Answer = u64;\n"));
+        assert!(rendered.contains("        Ok(input.raw().to_string())\n"));
+        // compiles whatever the answer type, String included
+        assert!(rendered.contains(
+            "    fn part1(_input: &Self::Parsed) -> Self::Answer {\n        Default::default()\n"
+        ));
+    }
+
+    #[test]
+    fn project_template_test() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(TEMPLATE_DIR)).unwrap();
+        fs::write(
+            dir.path().join("templates/grid.tpl"),
+            "// day {{day_number}}, {{input_type}}\nlet brace = format!(\"{{}}\");\n",
+        )
+        .unwrap();
+        let template = Template::load("grid", dir.path()).unwrap();
+
+        assert_eq!(
+            template.render(&placeholders()).unwrap(),
+            "// day 5, Vec<Vec<char>>\nlet brace = format!(\"{{}}\");\n"
+        );
+    }
+
+    #[test]
+    fn project_day_template_test() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(TEMPLATE_DIR)).unwrap();
+        fs::write(
+            dir.path().join("templates/day.tpl"),
+            "// edited\n{{parse_input}}\n",
+        )
+        .unwrap();
+
+        let template = Template::load("raw", dir.path()).unwrap();
+        assert_eq!(
+            template.render(&placeholders()).unwrap(),
+            "// edited\nOk(input.raw().to_string())\n"
+        );
+        assert!(Template::load("day", dir.path()).is_ok());
+        assert!(Template::load("fancy", dir.path()).is_err());
+    }
+
+    #[test]
+    fn unknown_template_test() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Template::load("fancy", dir.path()).is_err());
+
+        let template = Template {
+            source: "{{nope}}".to_string(),
+            ..Default::default()
+        };
+        assert!(template.render(&placeholders()).is_err());
+    }
+}
@@ -1,26 +1,22 @@
+use crate::project;
 use std::env::current_dir;
 use std::fs;
 use std::path::PathBuf;
 
 pub fn get_input(path: &str) -> String {
-    fs::read_to_string(get_path(path)).expect("Could not read input file")
-}
-
-pub fn parse_input(path: &str) -> Vec<String> {
-    get_input(path)
-        .lines()
-        .filter(|&line| !line.is_empty())
-        .map(|str| str.to_string())
-        .collect()
+    let path = get_path(path);
+    fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Could not read input file {}", path.display()))
 }
 
 fn get_path(path: &str) -> PathBuf {
-    let mut current = cwd();
+    let mut current = root();
     current.push(format!("src/{}", path));
 
     current
 }
 
-fn cwd() -> PathBuf {
-    current_dir().unwrap()
+// resolved the same way as the binary does, so tests can run from any directory in the crate
+fn root() -> PathBuf {
+    project::find_root(&current_dir().unwrap()).unwrap()
 }
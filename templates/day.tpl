@@ -0,0 +1,24 @@
+// {{link}}
+
+use crate::input::Input;
+use crate::solution::Solution;
+use anyhow::Result;
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Parsed = {{input_type}};
+    type Answer = {{answer_type}};
+
+    fn parse(input: &Input) -> Result<Self::Parsed> {
+        {{parse_input}}
+    }
+
+    fn part1(_input: &Self::Parsed) -> Self::Answer {
+        Default::default()
+    }
+
+    fn part2(_input: &Self::Parsed) -> Self::Answer {
+        Default::default()
+    }
+}
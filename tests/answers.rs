@@ -0,0 +1,117 @@
+//! Runs every answer in `answers.toml` through its registered solver, with the parameters it
+//! was recorded for, reporting each one the way libtest does. Any argument not starting with `-`
+//! filters entries by name. Real inputs aren't published, so an answer for one that hasn't been
+//! fetched is ignored, while a missing sample fails. Real inputs are read from the store
+//! `AOC_INPUT_DIR` and `AOC_INPUT_KEY` configure, like the binary does.
+
+use aoc_rust::answers::{Answers, Entry};
+use aoc_rust::store::InputStore;
+use aoc_rust::{project, puzzle, runner};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+enum Outcome {
+    Passed,
+    Failed(String),
+    Ignored(String),
+}
+
+fn check(root: &Path, inputs: &InputStore, entry: &Entry) -> Outcome {
+    let input = if entry.is_real_input() {
+        match inputs.read(root, entry.year, entry.day) {
+            Ok(Some(input)) => input,
+            Ok(None) => return Outcome::Ignored(puzzle::missing_input(entry.year, entry.day)),
+            Err(error) => return Outcome::Failed(format!("{:#}", error)),
+        }
+    } else {
+        let path = root.join(entry.input_path());
+        let Ok(input) = fs::read_to_string(&path) else {
+            return Outcome::Failed(format!("{} is missing", path.display()));
+        };
+        input
+    };
+
+    match runner::solve(entry.year, entry.day, entry.part, &input, &entry.params) {
+        Ok(solved) if solved.answer == entry.answer => Outcome::Passed,
+        Ok(solved) => Outcome::Failed(format!(
+            "expected {} but got {}",
+            entry.answer, solved.answer
+        )),
+        Err(error) => Outcome::Failed(error.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let root = project::find_root(&env::current_dir().unwrap()).unwrap();
+    let answers = Answers::load(&Answers::path(&root)).unwrap();
+    let inputs = InputStore::from_env();
+    let filters: Vec<_> = env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+
+    // a key that can't be read would otherwise leave its answers unchecked
+    let entries = match answers.entries() {
+        Ok(entries) => entries,
+        Err(error) => {
+            println!("\nerror: {:#}\n", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries: Vec<_> = entries
+        .into_iter()
+        .map(|entry| {
+            let mut name = format!(
+                "aoc{}::day{:02}::part{}::{}",
+                entry.year, entry.day, entry.part, entry.input
+            );
+            if !entry.params.is_empty() {
+                name = format!("{}::{}", name, entry.params);
+            }
+            (name, entry)
+        })
+        .filter(|(name, _)| filters.iter().all(|filter| name.contains(filter.as_str())))
+        .collect();
+
+    println!("\nrunning {} tests", entries.len());
+    let (mut passed, mut ignored, mut failures) = (0, 0, vec![]);
+    for (name, entry) in &entries {
+        match check(&root, &inputs, entry) {
+            Outcome::Passed => {
+                passed += 1;
+                println!("test {} ... ok", name);
+            }
+            Outcome::Ignored(reason) => {
+                ignored += 1;
+                println!("test {} ... ignored, {}", name, reason);
+            }
+            Outcome::Failed(reason) => {
+                println!("test {} ... FAILED", name);
+                failures.push((name, reason));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, reason) in &failures {
+            println!("    {}: {}", name, reason);
+        }
+    }
+    let result = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} ignored\n",
+        result,
+        passed,
+        failures.len(),
+        ignored
+    );
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
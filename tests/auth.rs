@@ -0,0 +1,112 @@
+mod common;
+
+use common::{Project, Stub};
+use std::fs;
+
+const HOME_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<div class="user">stubuser <span class="star-count">50*</span></div></div></header></body></html>
+"#;
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn auth_set_and_show_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["auth", "set", "53616c7465645f5f"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        fs::read_to_string(project.home(".aoc_cookie")).unwrap(),
+        "session=53616c7465645f5f\n"
+    );
+
+    let output = project.run(&stub, &["auth", "show"]);
+    assert!(stdout(&output).starts_with("5361********5f5f (from "));
+}
+
+#[test]
+fn auth_profiles_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+
+    project.run(
+        &stub,
+        &["--profile", "work", "auth", "set", "session=abcd1234wxyz"],
+    );
+    assert_eq!(
+        fs::read_to_string(project.home(".aoc_cookie.work")).unwrap(),
+        "session=abcd1234wxyz\n"
+    );
+
+    let output = project.run(&stub, &["auth", "show", "--profile", "work"]);
+    assert!(stdout(&output).starts_with("abcd****wxyz"));
+    let output = project.run(&stub, &["auth", "show"]);
+    assert!(stdout(&output).starts_with("****"), "{:?}", output);
+}
+
+#[test]
+fn auth_check_test() {
+    let stub = Stub::start(vec![("/", 200, HOME_PAGE.to_string())]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["auth", "whoami"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout(&output), "Logged in as stubuser\n");
+}
+
+#[test]
+fn auth_check_rejected_test() {
+    let logged_out = r#"<header><nav><a href="/2023/auth/login">[Log In]</a></nav></header>"#;
+    let stub = Stub::start(vec![("/", 200, logged_out.to_string())]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["auth", "check"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn auth_invalid_profile_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+
+    let output = project.run(
+        &stub,
+        &["--profile", "../../x", "auth", "set", "53616c7465645f5f"],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid profile name"));
+    assert!(!project.home("../../x").exists());
+    assert_eq!(fs::read_dir(project.home("")).unwrap().count(), 1);
+}
+
+#[test]
+fn auth_profile_over_env_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+    project.run(
+        &stub,
+        &["--profile", "work", "auth", "set", "session=abcd1234wxyz"],
+    );
+
+    let output = project
+        .command(
+            project.root(),
+            &stub,
+            &["--profile", "work", "auth", "show"],
+        )
+        .env("AOC_SESSION", "53616c7465645f5f")
+        .output()
+        .unwrap();
+    assert!(stdout(&output).starts_with("abcd****wxyz"), "{:?}", output);
+
+    let output = project
+        .command(project.root(), &stub, &["auth", "show"])
+        .env("AOC_SESSION", "53616c7465645f5f")
+        .output()
+        .unwrap();
+    assert_eq!(stdout(&output), "5361********5f5f (from AOC_SESSION)\n");
+}
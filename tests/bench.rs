@@ -0,0 +1,41 @@
+mod common;
+
+use common::{Project, Stub};
+use std::fs;
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn bench_baseline_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023/res")).unwrap();
+    fs::write(
+        project.path("src/aoc2023/res/day01.txt"),
+        "1abc2\npqr3stu8vwx\ntwo1nine\n",
+    )
+    .unwrap();
+
+    let args = ["bench", "--year", "2023", "--days", "1..=2", "-n", "3"];
+    let output = project.run(&stub, &args);
+    assert!(output.status.success(), "{:?}", output);
+    let table = stdout(&output);
+    assert!(table.starts_with("2023 benchmarks, 3 runs per part\n"));
+    assert_eq!(
+        table
+            .lines()
+            .filter(|line| line.starts_with("  1  "))
+            .count(),
+        2
+    );
+    assert!(table.contains("No input for day 2\n"));
+    assert!(!table.contains("Baseline total"));
+    assert!(project.exists("target/aoc-bench/2023.json"));
+
+    let output = project.run(&stub, &[&args[..], &["--no-save"]].concat());
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout(&output).contains("Baseline total"));
+    assert!(stub.requests().is_empty());
+}
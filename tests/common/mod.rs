@@ -0,0 +1,159 @@
+// shared by every integration test crate, each of which only uses some of it
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::TempDir;
+use tiny_http::{Response, Server};
+
+pub const PUZZLE_PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<article class="day-desc"><h2>--- Day 12: Stub Springs ---</h2>
+<p>Add up the numbers. For example:</p>
+<pre><code>1
+2
+3
+</code></pre>
+<p>In this example, the sum is <code><em>6</em></code>.</p>
+</article>
+</main></body></html>
+"#;
+
+/// An in-process stand in for the puzzle site, answering a fixed set of routes.
+pub struct Stub {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl Stub {
+    pub fn start(routes: Vec<(&'static str, u16, String)>) -> Stub {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let user_agent = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("User-Agent"))
+                    .map(|header| header.value.to_string())
+                    .unwrap_or_default();
+                let line = format!("{} {}", request.method(), request.url());
+                recorded.lock().unwrap().push((line, user_agent));
+
+                let response = match routes.iter().find(|(url, _, _)| *url == request.url()) {
+                    Some((_, status, body)) => {
+                        Response::from_string(body).with_status_code(*status)
+                    }
+                    None => Response::from_string("404 Not Found").with_status_code(404),
+                };
+                request.respond(response).unwrap();
+            }
+        });
+
+        Stub { base_url, requests }
+    }
+
+    /// A stub serving the input and description for 2023 day 12.
+    pub fn puzzle() -> Stub {
+        Stub::start(vec![
+            ("/2023/day/12/input", 200, "1\n2\n3\n4\n".to_string()),
+            ("/2023/day/12", 200, PUZZLE_PAGE.to_string()),
+        ])
+    }
+
+    /// Each request as `<method> <url>`.
+    pub fn requests(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|(line, _)| line.clone()).collect()
+    }
+
+    pub fn user_agents(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|(_, user_agent)| user_agent.clone())
+            .collect()
+    }
+}
+
+/// A throwaway crate to scaffold into, with its own home directory holding the cookie.
+pub struct Project {
+    dir: TempDir,
+    home: TempDir,
+}
+
+impl Project {
+    pub fn new() -> Project {
+        let dir = tempfile::tempdir().unwrap();
+        let home = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"stub\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub mod shared;\n").unwrap();
+        fs::write(home.path().join(".aoc_cookie"), "session=stub\n").unwrap();
+
+        Project { dir, home }
+    }
+
+    pub fn path(&self, path: &str) -> PathBuf {
+        self.dir.path().join(path)
+    }
+
+    pub fn cached(&self, path: &str) -> bool {
+        self.home(&format!("cache/{}", path)).exists()
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path(path)).unwrap()
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        Path::new(&self.path(path)).exists()
+    }
+
+    pub fn home(&self, path: &str) -> PathBuf {
+        self.home.path().join(path)
+    }
+
+    pub fn run(&self, stub: &Stub, args: &[&str]) -> Output {
+        self.run_in(self.dir.path(), stub, args)
+    }
+
+    /// Runs from `dir` rather than the crate root.
+    pub fn run_in(&self, dir: &Path, stub: &Stub, args: &[&str]) -> Output {
+        self.command(dir, stub, args).output().unwrap()
+    }
+
+    /// The command `run_in` runs, for adding to its environment.
+    pub fn command(&self, dir: &Path, stub: &Stub, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aoc-rust"));
+        command
+            .args(args)
+            .env_remove("AOC_SESSION")
+            .env_remove("AOC_PROFILE")
+            .env_remove("AOC_LEADERBOARD_ID")
+            .env_remove("AOC_ROOT")
+            .env_remove("AOC_INPUT_DIR")
+            .env_remove("AOC_INPUT_KEY")
+            .current_dir(dir)
+            .env("HOME", self.home.path())
+            .env("AOC_BASE_URL", &stub.base_url)
+            .env("AOC_CACHE_DIR", self.home.path().join("cache"))
+            .env("AOC_MIN_INTERVAL_MS", "0");
+
+        command
+    }
+
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{Project, Stub};
+use std::fs;
+
+#[test]
+fn import_into_input_dir_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023/res")).unwrap();
+    fs::write(
+        project.path("src/aoc2023/res/day01.txt"),
+        "1abc2\npqr3stu8vwx\n",
+    )
+    .unwrap();
+    fs::write(project.path("src/aoc2023/res/day01_sample.txt"), "1abc2\n").unwrap();
+    let inputs = project.home("inputs");
+
+    let output = project.run(&stub, &["import"]);
+    assert!(!output.status.success());
+
+    let input_dir = inputs.to_str().unwrap();
+    let output = project.run(&stub, &["import", "--input-dir", input_dir]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!project.exists("src/aoc2023/res/day01.txt"));
+    assert!(project.exists("src/aoc2023/res/day01_sample.txt"));
+    assert_eq!(
+        fs::read_to_string(inputs.join("2023/day01.txt")).unwrap(),
+        "1abc2\npqr3stu8vwx\n"
+    );
+
+    // everything reading inputs finds it in the store
+    let output = project.run(&stub, &["run", "-y", "2023", "-d", "1", "-p", "1"]);
+    assert!(!output.status.success());
+    let args = [
+        "run",
+        "-y",
+        "2023",
+        "-d",
+        "1",
+        "-p",
+        "1",
+        "--input-dir",
+        input_dir,
+    ];
+    let output = project.run(&stub, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("2023 day 1 part 1: 50 "));
+    assert!(stub.requests().is_empty());
+}
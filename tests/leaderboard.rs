@@ -0,0 +1,64 @@
+mod common;
+
+use common::{Project, Stub};
+use std::fs;
+
+const LEADERBOARD: &str = r#"{"event": "2023", "owner_id": 7, "members": {
+"7": {"id": 7, "name": "Owner", "stars": 2, "local_score": 4, "last_star_ts": 1701407430,
+"completion_day_level": {"1": {"1": {"get_star_ts": 1701407112}, "2": {"get_star_ts": 1701407430}}}},
+"9": {"id": 9, "name": null, "stars": 1, "local_score": 1, "last_star_ts": 1701410400,
+"completion_day_level": {"1": {"1": {"get_star_ts": 1701410400}}}}}}"#;
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn leaderboard_fetch_test() {
+    let stub = Stub::start(vec![(
+        "/2023/leaderboard/private/view/7.json",
+        200,
+        LEADERBOARD.to_string(),
+    )]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["leaderboard", "--id", "7", "--year", "2023"]);
+    assert!(output.status.success(), "{:?}", output);
+    let table = stdout(&output);
+    assert!(table.contains("   1      4      2  Owner\n"));
+    assert!(table.contains("   2      1      1  (anonymous user #9)\n"));
+    assert!(project.cached("2023/leaderboard_7.json"));
+
+    // fetched again within 15 minutes, so the cached copy is used
+    project.run(&stub, &["leaderboard", "--id", "7", "--year", "2023"]);
+    assert_eq!(
+        stub.requests(),
+        vec!["GET /2023/leaderboard/private/view/7.json"]
+    );
+}
+
+#[test]
+fn leaderboard_file_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+    fs::write(project.path("leaderboard.json"), LEADERBOARD).unwrap();
+
+    let output = project.run(&stub, &["leaderboard", "--file", "leaderboard.json"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout(&output).contains("day  1  Dec 01 00:05:12  Dec 01 00:10:30  +5m 18s"));
+    assert!(stub.requests().is_empty());
+}
+
+#[test]
+fn leaderboard_no_access_test() {
+    let stub = Stub::start(vec![(
+        "/2023/leaderboard/private/view/7.json",
+        200,
+        "<html><body>Leaderboards</body></html>".to_string(),
+    )]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["leaderboard", "--id", "7", "--year", "2023"]);
+    assert!(!output.status.success());
+    assert!(!project.cached("2023/leaderboard_7.json"));
+}
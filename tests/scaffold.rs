@@ -0,0 +1,418 @@
+mod common;
+
+use common::{Project, Stub, PUZZLE_PAGE};
+use std::fs;
+
+#[test]
+fn scaffold_new_day_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(project.read("src/aoc2023/res/day12.txt"), "1\n2\n3\n4\n\n");
+    assert_eq!(
+        project.read("src/aoc2023/res/day12_sample.txt"),
+        "1\n2\n3\n"
+    );
+    assert!(project
+        .read("src/aoc2023/res/day12.md")
+        .starts_with("## --- Day 12: Stub Springs ---"));
+    assert!(project
+        .read("src/aoc2023/day12.rs")
+        .starts_with("// https://adventofcode.com/2023/day/12\n"));
+    assert_eq!(
+        project.read("answers.toml"),
+        "[2023.day12.\"day12_sample.txt\"]\npart1 = \"6\"\n"
+    );
+    assert_eq!(project.read("src/aoc2023/mod.rs"), "pub mod day12;\n");
+    assert_eq!(
+        project.read("src/lib.rs"),
+        "pub mod aoc2023;\npub mod shared;\n"
+    );
+    assert_eq!(
+        stub.requests(),
+        vec!["GET /2023/day/12/input", "GET /2023/day/12"]
+    );
+}
+
+#[test]
+fn scaffold_rerun_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+    fs::write(project.path("src/aoc2023/day12.rs"), "// solved\n").unwrap();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(project.read("src/aoc2023/day12.rs"), "// solved\n");
+    assert_eq!(project.read("src/aoc2023/mod.rs"), "pub mod day12;\n");
+    assert_eq!(
+        project.read("src/lib.rs"),
+        "pub mod aoc2023;\npub mod shared;\n"
+    );
+    let input_requests = stub
+        .requests()
+        .iter()
+        .filter(|request| request.ends_with("/input"))
+        .count();
+    assert_eq!(input_requests, 1);
+}
+
+#[test]
+fn scaffold_force_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+    fs::write(project.path("src/aoc2023/day12.rs"), "// solved\n").unwrap();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12", "--force"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("overwrite src/aoc2023/day12.rs"));
+    assert!(project
+        .read("src/aoc2023/day12.rs")
+        .contains("impl Solution for Day12"));
+    assert_eq!(project.read("src/aoc2023/mod.rs"), "pub mod day12;\n");
+}
+
+#[test]
+fn scaffold_dry_run_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12", "--dry-run"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "\
+Dry run, no files were changed
+   create src/aoc2023/res/day12.txt
+   create src/aoc2023/res/day12.md
+   create src/aoc2023/res/day12_sample.txt
+   create answers.toml
+   create src/aoc2023/day12.rs
+   create src/aoc2023/mod.rs
+   update src/lib.rs
+"
+    );
+    assert!(!project.exists("src/aoc2023"));
+    assert!(!project.exists("answers.toml"));
+    assert_eq!(project.read("src/lib.rs"), "pub mod shared;\n");
+    assert_eq!(stub.requests(), vec!["GET /2023/day/12"]);
+}
+
+#[test]
+fn scaffold_dry_run_existing_day_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12", "--dry-run"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "\
+Dry run, no files were changed
+     skip src/aoc2023/res/day12.txt
+   update src/aoc2023/res/day12.md
+     skip src/aoc2023/res/day12_sample.txt
+     skip src/aoc2023/day12.rs
+     skip src/aoc2023/mod.rs
+     skip src/lib.rs
+"
+    );
+}
+
+#[test]
+fn scaffold_dry_run_forced_samples_test() {
+    let page = PUZZLE_PAGE.replace(
+        "</article>",
+        "<p>Another example:</p>\n<pre><code>4\n5\n</code></pre>\n</article>",
+    );
+    let stub = Stub::start(vec![
+        ("/2023/day/12/input", 200, "1\n2\n3\n4\n".to_string()),
+        ("/2023/day/12", 200, page),
+    ]);
+    let project = Project::new();
+    project.run(&Stub::puzzle(), &["--year", "2023", "--day", "12"]);
+    let day = project.read("src/aoc2023/day12.rs");
+
+    let output = project.run(
+        &stub,
+        &["--year", "2023", "--day", "12", "--dry-run", "--force"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "\
+Dry run, no files were changed
+overwrite src/aoc2023/res/day12.txt
+   update src/aoc2023/res/day12.md
+overwrite src/aoc2023/res/day12_sample.txt
+   create src/aoc2023/res/day12_sample2.txt
+overwrite src/aoc2023/day12.rs
+     skip src/aoc2023/mod.rs
+     skip src/lib.rs
+"
+    );
+    assert!(!project.exists("src/aoc2023/res/day12_sample2.txt"));
+    assert_eq!(project.read("src/aoc2023/day12.rs"), day);
+}
+
+#[test]
+fn scaffold_expired_cookie_test() {
+    let logged_out = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+    let stub = Stub::start(vec![("/2023/day/12/input", 400, logged_out.to_string())]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("expired"), "{}", stderr);
+    assert!(!project.exists("src/aoc2023/res/day12.txt"));
+    assert!(!project.exists("src/aoc2023/day12.rs"));
+}
+
+#[test]
+fn scaffold_locked_day_keeps_input_test() {
+    let locked = "Please don't repeatedly request this endpoint before it unlocks!\n";
+    let stub = Stub::start(vec![("/2023/day/12/input", 404, locked.to_string())]);
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023/res")).unwrap();
+    fs::write(project.path("src/aoc2023/res/day12.txt"), "1\n2\n").unwrap();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12", "--force"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("2023 day 12 isn't unlocked yet"),
+        "{}",
+        stderr
+    );
+    assert_eq!(project.read("src/aoc2023/res/day12.txt"), "1\n2\n");
+}
+
+#[test]
+fn scaffold_cached_input_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+    assert!(project.cached("2023/day12.txt"));
+    fs::remove_file(project.path("src/aoc2023/res/day12.txt")).unwrap();
+
+    let output = project.run(&stub, &["--year", "2023", "--day", "12"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    assert_eq!(project.read("src/aoc2023/res/day12.txt"), "1\n2\n3\n4\n\n");
+    let input_requests = stub
+        .requests()
+        .iter()
+        .filter(|request| request.ends_with("/input"))
+        .count();
+    assert_eq!(input_requests, 1);
+}
+
+#[test]
+fn scaffold_cached_input_per_profile_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    fs::write(project.home(".aoc_cookie.work"), "session=work\n").unwrap();
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+    fs::remove_file(project.path("src/aoc2023/res/day12.txt")).unwrap();
+
+    let output = project.run(
+        &stub,
+        &["--profile", "work", "--year", "2023", "--day", "12"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    assert!(project.cached("2023/day12.txt"));
+    assert!(project.cached("profiles/work/2023/day12.txt"));
+    let input_requests = stub
+        .requests()
+        .iter()
+        .filter(|request| request.ends_with("/input"))
+        .count();
+    assert_eq!(input_requests, 2);
+}
+
+#[test]
+fn scaffold_encrypted_input_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    let key = project.home("input.key");
+
+    let args = ["--year", "2023", "--day", "12", "--input-key"];
+    let output = project.run(&stub, &[&args[..], &[key.to_str().unwrap()]].concat());
+    assert!(output.status.success(), "{:?}", output);
+
+    let encrypted = fs::read(project.path("src/aoc2023/res/day12.txt.enc")).unwrap();
+    assert!(!String::from_utf8_lossy(&encrypted).contains("1\n2\n3\n4"));
+    // no plain copy anywhere, neither in the crate nor in the cache
+    assert!(!project.exists("src/aoc2023/res/day12.txt"));
+    assert!(!project.cached("2023/day12.txt"));
+}
+
+#[test]
+fn scaffold_user_agent_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+
+    project.run(&stub, &["--year", "2023", "--day", "12"]);
+    project.run(
+        &stub,
+        &[
+            "--year",
+            "2023",
+            "--day",
+            "12",
+            "--user-agent",
+            "me@example.com",
+        ],
+    );
+
+    let user_agents = stub.user_agents();
+    assert!(user_agents[0].starts_with("aoc-rust/"));
+    assert_eq!(user_agents.last().unwrap(), "me@example.com");
+}
+
+#[test]
+fn scaffold_template_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+
+    let args = ["--year", "2023", "--day", "12", "--template", "raw"];
+    let output = project.run(&stub, &[&args[..], &["--answer-type", "u64"]].concat());
+    assert!(output.status.success(), "{:?}", output);
+
+    let day = project.read("src/aoc2023/day12.rs");
+    assert!(day.contains("    type Parsed = String;\n    type Answer = u64;\n"));
+    assert!(day.contains("    fn part2(_input: &Self::Parsed) -> Self::Answer {"));
+}
+
+#[test]
+fn scaffold_project_template_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    fs::create_dir_all(project.path("templates")).unwrap();
+    fs::write(
+        project.path("templates/mine.tpl"),
+        "// {{year}} day {{day_number}}: {{part1_sample}}\n",
+    )
+    .unwrap();
+
+    let output = project.run(
+        &stub,
+        &["--year", "2023", "--day", "12", "--template", "mine"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(project.read("src/aoc2023/day12.rs"), "// 2023 day 12: 6\n");
+
+    let output = project.run(
+        &stub,
+        &["--year", "2023", "--day", "13", "--template", "nope"],
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn scaffold_from_subdirectory_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023")).unwrap();
+
+    let output = project.run_in(
+        &project.path("src/aoc2023"),
+        &stub,
+        &["--year", "2023", "--day", "12"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert!(project.exists("src/aoc2023/day12.rs"));
+    assert!(project.exists("src/aoc2023/res/day12.txt"));
+    assert!(!project.exists("src/aoc2023/src"));
+}
+
+#[test]
+fn scaffold_root_flag_test() {
+    let stub = Stub::puzzle();
+    let project = Project::new();
+    let elsewhere = tempfile::tempdir().unwrap();
+    let root = project.path("").display().to_string();
+
+    let args = ["--root", &root, "--year", "2023", "--day", "12"];
+    let output = project.run_in(elsewhere.path(), &stub, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(project.exists("src/aoc2023/day12.rs"));
+
+    let output = project.run_in(elsewhere.path(), &stub, &["--year", "2023", "--day", "12"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn scaffold_days_test() {
+    let page = common::PUZZLE_PAGE.to_string();
+    let stub = Stub::start(vec![
+        ("/2023/day/1/input", 200, "1\n".to_string()),
+        ("/2023/day/1", 200, page.clone()),
+        (
+            "/2023/day/2/input",
+            500,
+            "Internal Server Error".to_string(),
+        ),
+        ("/2023/day/3/input", 200, "3\n".to_string()),
+        ("/2023/day/3", 200, page.clone()),
+        ("/2023/day/4/input", 200, "4\n".to_string()),
+        ("/2023/day/4", 200, page),
+    ]);
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023/res")).unwrap();
+    fs::write(project.path("src/aoc2023/day03.rs"), "// solved\n").unwrap();
+    fs::write(project.path("src/aoc2023/res/day03.txt"), "3\n").unwrap();
+
+    let output = project.run(&stub, &["--year", "2023", "--days", "4,1..=3"]);
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("  day  1  succeeded\n"), "{}", stdout);
+    assert!(stdout.contains("  day  2  failed: Request for 2023 day 2 failed with 500"));
+    assert!(stdout.contains("  day  3  skipped\n"));
+    assert!(stdout.contains("  day  4  succeeded\n"));
+    assert!(stdout.contains("2 succeeded, 1 skipped, 1 failed\n"));
+
+    assert_eq!(
+        project.read("src/aoc2023/mod.rs"),
+        "pub mod day01;\npub mod day03;\npub mod day04;\n"
+    );
+    assert_eq!(
+        project.read("src/lib.rs"),
+        "pub mod aoc2023;\npub mod shared;\n"
+    );
+    assert_eq!(project.read("src/aoc2023/day03.rs"), "// solved\n");
+    assert!(!project.exists("src/aoc2023/day02.rs"));
+}
+
+#[test]
+fn scaffold_days_conflicts_test() {
+    let stub = Stub::start(vec![]);
+    let project = Project::new();
+
+    let output = project.run(&stub, &["--day", "1", "--days", "1..=3"]);
+    assert!(!output.status.success());
+    let output = project.run(&stub, &["--all-unlocked", "--days", "1..=3"]);
+    assert!(!output.status.success());
+    let output = project.run(&stub, &["--days", "1..=26"]);
+    assert!(!output.status.success());
+    assert!(stub.requests().is_empty());
+}
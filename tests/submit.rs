@@ -0,0 +1,36 @@
+mod common;
+
+use common::{Project, Stub, PUZZLE_PAGE};
+use std::fs;
+
+const CORRECT: &str =
+    "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>";
+
+#[test]
+fn submit_records_answer_test() {
+    let stub = Stub::start(vec![
+        ("/2023/day/12/answer", 200, CORRECT.to_string()),
+        ("/2023/day/12", 200, PUZZLE_PAGE.to_string()),
+    ]);
+    let project = Project::new();
+    fs::create_dir_all(project.path("src/aoc2023/res")).unwrap();
+    fs::write(
+        project.path("answers.toml"),
+        "[2023.day12.\"day12_sample.txt\"]\npart1 = \"6\"\n",
+    )
+    .unwrap();
+
+    let args = [
+        "submit", "--year", "2023", "--day", "12", "--part", "1", "10",
+    ];
+    let output = project.run(&stub, &args);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        project.read("answers.toml"),
+        "[2023.day12.\"day12.txt\"]\npart1 = \"10\"\n\n[2023.day12.\"day12_sample.txt\"]\npart1 = \"6\"\n"
+    );
+    assert_eq!(
+        stub.requests(),
+        vec!["POST /2023/day/12/answer", "GET /2023/day/12"]
+    );
+}